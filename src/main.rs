@@ -3,6 +3,7 @@ mod ui_components;
 mod utils;
 
 use crate::ui_components::MainWindow;
+use crate::utils::get_settings;
 use dirs::data_local_dir;
 use eframe::{egui, NativeOptions};
 use egui::{vec2, ViewportBuilder};
@@ -38,9 +39,13 @@ fn main() {
         set_current_dir(target_location).unwrap();
 
         info!("Starting app");
+        let settings = get_settings();
+        let start_size = settings
+            .saved_window_size(&settings.start_tab)
+            .unwrap_or(vec2(600.0, 450.0));
         let viewport = ViewportBuilder::default()
             .with_title("Talon")
-            .with_inner_size(vec2(600.0, 450.0))
+            .with_inner_size(start_size)
             .with_resizable(true)
             .with_maximize_button(false);
         let native_options = NativeOptions {
@@ -1,17 +1,43 @@
 use grammers_client::types::PackedChat;
 use log::{error, info};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
 
-use crate::tg_handler::{ProcessError, ProcessResult, TGClient};
-use crate::ui_components::processor::UnpackedBlacklistedUser;
+use crate::tg_handler::{ProcessError, ProcessResult, TGClient, LOAD_PROGRESS_INTERVAL};
+use crate::ui_components::processor::{PackedBlacklistedUser, UnpackedBlacklistedUser};
 
 impl TGClient {
     /// Unpacks existing `PackedChat` hex string and sends it to the GUI
-    pub async fn load_blacklisted_users(&self, hex_data: Vec<String>) -> Result<(), ProcessError> {
+    pub async fn load_blacklisted_users(
+        &self,
+        hex_data: Vec<String>,
+        cancel: Arc<AtomicBool>,
+    ) -> Result<(), ProcessError> {
         info!("Starting unpacking chat by {}", self.name());
         let mut chat_list = Vec::new();
         let mut failed_chat_num = 0;
+        let mut processed_since_update = 0;
+        let mut unloaded = Vec::new();
+
+        let mut hex_iter = hex_data.into_iter();
+        while let Some(hex) = hex_iter.next() {
+            // Stop unpacking and keep whatever was loaded so far. The rest are sent back so they
+            // are not removed from the saved list
+            if cancel.load(Ordering::Acquire) {
+                info!("Blacklist loading cancelled by {}", self.name());
+                unloaded = std::iter::once(hex)
+                    .chain(hex_iter)
+                    .map(|hex| PackedBlacklistedUser::new(hex, self.name()))
+                    .collect();
+                break;
+            }
+
+            if processed_since_update == LOAD_PROGRESS_INTERVAL {
+                self.send(ProcessResult::BlacklistLoadProgress(processed_since_update));
+                processed_since_update = 0;
+            }
+            processed_since_update += 1;
 
-        for hex in hex_data {
             let packed_chat_result = PackedChat::from_hex(&hex);
 
             if let Ok(packed_chat) = packed_chat_result {
@@ -29,7 +55,12 @@ impl TGClient {
             }
         }
 
-        self.send(ProcessResult::UnpackedBlacklist(chat_list, failed_chat_num));
+        self.send(ProcessResult::BlacklistLoadProgress(processed_since_update));
+        self.send(ProcessResult::UnpackedBlacklist(
+            chat_list,
+            failed_chat_num,
+            unloaded,
+        ));
         Ok(())
     }
 
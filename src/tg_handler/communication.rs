@@ -1,29 +1,55 @@
 use grammers_client::types::iter_buffer::InvocationError;
 use grammers_client::types::{LoginToken, PasswordToken};
 use grammers_mtsender::AuthorizationError;
+use std::collections::HashSet;
 use std::sync::atomic::AtomicBool;
 use std::sync::Arc;
 use tokio::sync::Mutex;
 
 use crate::tg_handler::{TGClient, TGCountData};
-use crate::ui_components::processor::{UnpackedBlacklistedUser, UnpackedWhitelistedUser};
+use crate::ui_components::processor::{
+    MessageCap, MessageFilter, PackedBlacklistedUser, PackedWhitelistedUser, SenderFilter,
+    UnpackedBlacklistedUser, UnpackedWhitelistedUser,
+};
+
+/// How many saved users to unpack before sending a progress update to the GUI
+pub const LOAD_PROGRESS_INTERVAL: usize = 20;
 
 pub enum ProcessResult {
     InitialSessionSuccess((Vec<TGClient>, Vec<String>, Vec<String>)),
     InvalidChat(String),
     UnauthorizedClient(String),
-    CountingMessage(TGCountData),
+    CountingMessage(Box<TGCountData>),
     CountingEnd((i32, i32)),
     ProcessFailed(ProcessError),
     LoginCodeSent(LoginToken, TGClient),
     PasswordRequired(Box<PasswordToken>),
     LoggedIn(String),
-    UnpackedWhitelist(Vec<UnpackedWhitelistedUser>, i32),
-    UnpackedBlacklist(Vec<UnpackedBlacklistedUser>, i32),
-    FloodWait,
+    /// Unpacked users, failed user count, saved users that were not loaded due to cancelling
+    UnpackedWhitelist(
+        Vec<UnpackedWhitelistedUser>,
+        i32,
+        Vec<PackedWhitelistedUser>,
+    ),
+    /// Unpacked users, failed user count, saved users that were not loaded due to cancelling
+    UnpackedBlacklist(
+        Vec<UnpackedBlacklistedUser>,
+        i32,
+        Vec<PackedBlacklistedUser>,
+    ),
+    /// Number of whitelisted users processed since the last update
+    WhitelistLoadProgress(usize),
+    /// Number of blacklisted users processed since the last update
+    BlacklistLoadProgress(usize),
+    /// Seconds to wait if known
+    FloodWait(Option<u32>),
     WhiteListUser(UnpackedWhitelistedUser),
     BlackListUser(UnpackedBlacklistedUser),
     ChatExists(String, i32, i32),
+    /// Chat name, IDs of the current members, total members reported by Telegram
+    ChatMembers(String, HashSet<i64>, usize),
+    /// Chat name, IDs of the admins and the owner
+    ChatAdmins(String, HashSet<i64>),
 }
 
 #[derive(Debug)]
@@ -36,22 +62,46 @@ pub enum ProcessError {
     InvalidPhoneOrAPI(AuthorizationError),
     InvalidAPIKeys,
     FailedLatestMessage,
+    /// The chat exists but the session cannot read its messages
+    PrivateChat(String),
+    /// The members of the chat cannot be listed by the session
+    MembersUnavailable(String),
+    /// The admins of the chat cannot be listed by the session
+    AdminsUnavailable(String),
     UnknownError(InvocationError),
 }
 
 /// Used by `TGClient` struct to handle operations
 pub enum ProcessStart {
-    /// Start chat, start num, end num, multi session, whether to cancel
-    StartCount(String, Option<i32>, Option<i32>, bool, Arc<AtomicBool>),
+    /// Start chat, start num, end num, multi session, whether to cancel, message filter, sender
+    /// filter, message cap, whether to skip service messages, whether to skip stickers
+    StartCount(
+        String,
+        Option<i32>,
+        Option<i32>,
+        bool,
+        Arc<AtomicBool>,
+        Option<MessageFilter>,
+        Option<SenderFilter>,
+        Option<MessageCap>,
+        bool,
+        bool,
+    ),
     SignInCode(Arc<Mutex<LoginToken>>, String),
     SignInPasswords(Arc<Mutex<PasswordToken>>, String),
     SessionLogout,
-    LoadWhitelistedUsers(Vec<String>),
-    LoadBlacklistedUsers(Vec<String>),
+    /// Saved hex data, whether to cancel
+    LoadWhitelistedUsers(Vec<String>, Arc<AtomicBool>),
+    /// Saved hex data, whether to cancel
+    LoadBlacklistedUsers(Vec<String>, Arc<AtomicBool>),
     NewWhitelistUser(String),
     NewBlacklistUser(String),
     /// Start chat, start num, end num
     CheckChatExistence(String, Option<i32>, Option<i32>),
+    /// Chat name
+    FetchChatMembers(String),
+    /// Chat name
+    FetchAdmins(String),
 }
 
 /// Used when trying to create a new `TGClient` by processing some operations
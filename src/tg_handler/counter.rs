@@ -1,11 +1,28 @@
+use grammers_client::types::iter_buffer::InvocationError;
 use grammers_client::types::Message;
-use log::info;
+use log::{info, warn};
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::{Arc, Mutex};
 use std::thread::{self, sleep};
 use std::time::{Duration, Instant};
 
 use crate::tg_handler::{ProcessError, ProcessResult, TGClient};
+use crate::ui_components::processor::{MessageCap, MessageFilter, SenderFilter};
+use crate::utils::is_sticker_or_gif;
+
+/// Maximum number of consecutive flood waits to retry before giving up on the count
+const MAX_FLOOD_RETRIES: u32 = 5;
+
+/// How often cancelling is checked while waiting out a flood wait
+const FLOOD_WAIT_STEP: Duration = Duration::from_millis(250);
+
+/// RPC errors returned when the session is not allowed to read the messages of a chat
+const PRIVATE_CHAT_ERRORS: [&str; 4] = [
+    "CHANNEL_PRIVATE",
+    "CHAT_FORBIDDEN",
+    "CHANNEL_PUBLIC_GROUP_NA",
+    "USER_NOT_PARTICIPANT",
+];
 
 pub struct TGCountData {
     name: String,
@@ -61,6 +78,7 @@ impl TGCountData {
 
 impl TGClient {
     /// Iters through a Telegram chat from a given point to the first message or until the end point is reached
+    #[allow(clippy::too_many_arguments)]
     pub async fn start_count(
         &self,
         start_chat: String,
@@ -68,6 +86,11 @@ impl TGClient {
         end_num: Option<i32>,
         multi_session: bool,
         cancel: Arc<AtomicBool>,
+        filter: Option<MessageFilter>,
+        sender_filter: Option<SenderFilter>,
+        cap: Option<MessageCap>,
+        skip_service: bool,
+        skip_stickers: bool,
     ) -> Result<(), ProcessError> {
         if !self.check_authorization().await? {
             return Ok(());
@@ -109,7 +132,7 @@ impl TGClient {
             if let Some(last_sent) = *last_sent {
                 let time_passed = last_sent.elapsed().as_millis();
                 if time_passed > 500 && time_passed < 1050 {
-                    sender.send(ProcessResult::FloodWait).unwrap();
+                    sender.send(ProcessResult::FloodWait(None)).unwrap();
                     context.request_repaint();
                 };
 
@@ -133,11 +156,51 @@ impl TGClient {
             last_number = start_at + 1;
         }
 
-        while let Some(message) = iter_message
-            .next()
-            .await
-            .map_err(ProcessError::UnknownError)?
-        {
+        let mut flood_retries = 0;
+        let mut cap_reached = false;
+
+        loop {
+            let message = match iter_message.next().await {
+                Ok(Some(message)) => message,
+                Ok(None) => break,
+                // Flood waits below the client threshold are handled by grammers itself.
+                // Longer ones are retried here with an increasing extra delay
+                Err(InvocationError::Rpc(e))
+                    if e.is("FLOOD_WAIT") && flood_retries < MAX_FLOOD_RETRIES =>
+                {
+                    let wait_seconds = e.value.unwrap_or_default() + 2_u32.pow(flood_retries);
+                    flood_retries += 1;
+
+                    warn!(
+                        "{} flood wait triggered. Retrying after {wait_seconds} seconds",
+                        self.name()
+                    );
+                    self.send(ProcessResult::FloodWait(Some(wait_seconds)));
+
+                    let wait = Duration::from_secs(u64::from(wait_seconds));
+
+                    // Prevent the watcher thread from reporting the wait as an unknown flood wait
+                    {
+                        let mut last_sent_lock = last_sent.lock().unwrap();
+                        *last_sent_lock = Some(Instant::now() + wait);
+                    }
+
+                    // Wait in short steps so cancelling does not have to wait out the flood wait
+                    let wait_until = Instant::now() + wait;
+                    while Instant::now() < wait_until && !cancel.load(Ordering::Acquire) {
+                        let remaining = wait_until.saturating_duration_since(Instant::now());
+                        tokio::time::sleep(remaining.min(FLOOD_WAIT_STEP)).await;
+                    }
+                    if cancel.load(Ordering::Acquire) {
+                        info!("{} count cancelled during a flood wait", self.name());
+                        break;
+                    }
+                    continue;
+                }
+                Err(e) => return Err(message_error(&start_chat, e)),
+            };
+            flood_retries = 0;
+
             let message_num = message.id();
             if start_at == -1 {
                 info!("Setting starting point as {message_num}");
@@ -149,7 +212,29 @@ impl TGClient {
                 break;
             }
 
-            if message_num <= start_at {
+            // Messages that do not match the filters, service messages such as joins or pins and
+            // stickers when skipped still update the last number so they are not considered as
+            // deleted
+            let is_service = message.action().is_some();
+            let filtered_out = (skip_service && is_service)
+                || (skip_stickers && is_sticker_or_gif(&message))
+                || filter
+                    .as_ref()
+                    .is_some_and(|filter| !filter.matches(message.text()))
+                || sender_filter
+                    .as_ref()
+                    .is_some_and(|filter| !filter.matches(&message));
+
+            if message_num <= start_at && filtered_out {
+                last_number = message_num;
+            } else if message_num <= start_at {
+                // The cap is shared between all sessions counting this chat
+                if cap.as_ref().is_some_and(|cap| !cap.take()) {
+                    info!("{} stopping as the message cap was reached", self.name());
+                    cap_reached = true;
+                    break;
+                }
+
                 let count_data = TGCountData::new(
                     self.name(),
                     message,
@@ -158,7 +243,7 @@ impl TGClient {
                     last_number,
                     multi_session,
                 );
-                self.send(ProcessResult::CountingMessage(count_data));
+                self.send(ProcessResult::CountingMessage(Box::new(count_data)));
                 last_number = message_num;
             }
 
@@ -178,6 +263,9 @@ impl TGClient {
         let mut last_sent_lock = last_sent.lock().unwrap();
         *last_sent_lock = None;
 
+        // Messages after the cap were never reached so they must not be counted as deleted
+        let end_at = if cap_reached { last_number } else { end_at };
+
         self.send(ProcessResult::CountingEnd((end_at, last_number)));
         Ok(())
     }
@@ -212,7 +300,7 @@ impl TGClient {
             if let Some(message) = iter_message
                 .next()
                 .await
-                .map_err(ProcessError::UnknownError)?
+                .map_err(|e| message_error(&start_chat, e))?
             {
                 message.id()
             } else {
@@ -228,3 +316,14 @@ impl TGClient {
         Ok(())
     }
 }
+
+/// Converts an error acquired while reading the messages of a chat, separating the chats the
+/// session does not have access to from the unknown errors
+fn message_error(chat_name: &str, error: InvocationError) -> ProcessError {
+    match &error {
+        InvocationError::Rpc(e) if PRIVATE_CHAT_ERRORS.iter().any(|name| e.is(name)) => {
+            ProcessError::PrivateChat(chat_name.to_string())
+        }
+        _ => ProcessError::UnknownError(error),
+    }
+}
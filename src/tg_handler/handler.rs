@@ -7,6 +7,7 @@ use std::sync::mpsc::Sender;
 use crate::tg_handler::{
     connect_to_session, send_login_code, NewProcess, ProcessError, ProcessResult, ProcessStart,
 };
+use crate::utils::SAVED_MESSAGES_CHAT;
 
 #[derive(Clone)]
 pub struct TGClient {
@@ -37,26 +38,50 @@ impl TGClient {
     /// Start an operation with a telegram client
     pub async fn start_process(self, process_type: ProcessStart) {
         let result = match process_type {
-            ProcessStart::StartCount(start_chat, start_num, end_num, multi_session, cancel) => {
-                self.start_count(start_chat, start_num, end_num, multi_session, cancel)
-                    .await
+            ProcessStart::StartCount(
+                start_chat,
+                start_num,
+                end_num,
+                multi_session,
+                cancel,
+                filter,
+                sender_filter,
+                cap,
+                skip_service,
+                skip_stickers,
+            ) => {
+                self.start_count(
+                    start_chat,
+                    start_num,
+                    end_num,
+                    multi_session,
+                    cancel,
+                    filter,
+                    sender_filter,
+                    cap,
+                    skip_service,
+                    skip_stickers,
+                )
+                .await
             }
             ProcessStart::SignInCode(token, code) => self.sign_in_code(token, code).await,
             ProcessStart::SignInPasswords(token, password) => {
                 self.sign_in_password(token, password).await
             }
             ProcessStart::SessionLogout => self.logout().await,
-            ProcessStart::LoadWhitelistedUsers(hex_data) => {
-                self.load_whitelisted_users(hex_data).await
+            ProcessStart::LoadWhitelistedUsers(hex_data, cancel) => {
+                self.load_whitelisted_users(hex_data, cancel).await
             }
             ProcessStart::NewWhitelistUser(name) => self.new_whitelist(name).await,
-            ProcessStart::LoadBlacklistedUsers(hex_data) => {
-                self.load_blacklisted_users(hex_data).await
+            ProcessStart::LoadBlacklistedUsers(hex_data, cancel) => {
+                self.load_blacklisted_users(hex_data, cancel).await
             }
             ProcessStart::NewBlacklistUser(name) => self.new_blacklist(name).await,
             ProcessStart::CheckChatExistence(name, start, end) => {
                 self.check_chat_status(name, start, end).await
             }
+            ProcessStart::FetchChatMembers(name) => self.fetch_chat_members(name).await,
+            ProcessStart::FetchAdmins(name) => self.fetch_chat_admins(name).await,
         };
 
         if let Err(err) = result {
@@ -110,6 +135,20 @@ impl TGClient {
 
     /// Tries to resolve a username to get a Telegram chat account
     pub async fn check_username(&self, chat_name: &str) -> Result<Chat, ProcessResult> {
+        if chat_name == SAVED_MESSAGES_CHAT {
+            let Ok(me) = self.client().get_me().await else {
+                error!("Failed to get the session user");
+                return Err(ProcessResult::InvalidChat(chat_name.to_owned()));
+            };
+            info!("Target chat is the Saved Messages of {}", me.full_name());
+            return Ok(Chat::User(me));
+        }
+
+        // Usernames cannot start with a number so a numeric name is a private channel or user ID
+        if let Ok(chat_id) = chat_name.parse::<i64>() {
+            return self.find_dialog_chat(chat_name, chat_id).await;
+        }
+
         let tg_chat = self.client().resolve_username(chat_name).await;
 
         let Ok(tg_chat) = tg_chat else {
@@ -127,6 +166,34 @@ impl TGClient {
         Ok(tg_chat)
     }
 
+    /// Searches the dialogs of the client for a private channel or a DM with the given ID.
+    /// These cannot be resolved by their ID unless the session already has the dialog
+    async fn find_dialog_chat(&self, chat_name: &str, chat_id: i64) -> Result<Chat, ProcessResult> {
+        let mut dialogs = self.client().iter_dialogs();
+
+        loop {
+            match dialogs.next().await {
+                Ok(Some(dialog)) => {
+                    let chat = dialog.chat();
+                    if chat.id() == chat_id {
+                        info!("Target chat {} exist", chat.name());
+                        return Ok(chat.clone());
+                    }
+                }
+                Ok(None) => break,
+                Err(e) => {
+                    error!("Failed to iterate dialogs. Error: {e}");
+                    return Err(ProcessResult::InvalidChat(chat_name.to_owned()));
+                }
+            }
+        }
+
+        error!("No joined chat found with the ID {chat_id}");
+        Err(ProcessResult::ProcessFailed(ProcessError::PrivateChat(
+            chat_name.to_owned(),
+        )))
+    }
+
     /// Logs out of the client
     pub async fn logout(&self) -> Result<(), ProcessError> {
         let _ = self.client().sign_out().await;
@@ -0,0 +1,101 @@
+use grammers_client::grammers_tl_types as tl;
+use grammers_client::types::Role;
+use log::{error, info};
+use std::collections::HashSet;
+
+use crate::tg_handler::{ProcessError, ProcessResult, TGClient};
+
+impl TGClient {
+    /// Fetches the IDs of the users that are currently members of the chat and sends them to
+    /// the GUI along with the member count reported by Telegram
+    pub async fn fetch_chat_members(&self, chat_name: String) -> Result<(), ProcessError> {
+        info!("Fetching members of {chat_name} by {}", self.name());
+
+        // Channels and chats with hidden members do not allow listing the members
+        let Some((members, total)) = self
+            .participant_ids(
+                &chat_name,
+                None,
+                |role| !matches!(role, Role::Banned(_) | Role::Left(_)),
+                ProcessError::MembersUnavailable,
+            )
+            .await?
+        else {
+            return Ok(());
+        };
+
+        info!("Fetched {} members out of {total}", members.len());
+        self.send(ProcessResult::ChatMembers(chat_name, members, total));
+        Ok(())
+    }
+
+    /// Fetches the IDs of the admins and the owner of the chat and sends them to the GUI
+    pub async fn fetch_chat_admins(&self, chat_name: String) -> Result<(), ProcessError> {
+        info!("Fetching admins of {chat_name} by {}", self.name());
+
+        // The filter only applies to channels, small groups list every member so the role is
+        // checked as well
+        let Some((admins, _)) = self
+            .participant_ids(
+                &chat_name,
+                Some(tl::enums::ChannelParticipantsFilter::ChannelParticipantsAdmins),
+                |role| matches!(role, Role::Creator(_) | Role::Admin(_)),
+                ProcessError::AdminsUnavailable,
+            )
+            .await?
+        else {
+            return Ok(());
+        };
+
+        info!("Fetched {} admins", admins.len());
+        self.send(ProcessResult::ChatAdmins(chat_name, admins));
+        Ok(())
+    }
+
+    /// Resolves the chat and collects the IDs of the participants with a matching role along
+    /// with the participant count reported by Telegram. Returns None if the chat could not be
+    /// resolved, in which case the reason is already sent to the GUI
+    async fn participant_ids(
+        &self,
+        chat_name: &str,
+        filter: Option<tl::enums::ChannelParticipantsFilter>,
+        keep: fn(&Role) -> bool,
+        unavailable: fn(String) -> ProcessError,
+    ) -> Result<Option<(HashSet<i64>, usize)>, ProcessError> {
+        if !self.check_authorization().await? {
+            return Ok(None);
+        }
+
+        let tg_chat = match self.check_username(chat_name).await {
+            Ok(chat) => chat,
+            Err(e) => {
+                self.send(e);
+                return Ok(None);
+            }
+        };
+
+        let mut participants = self.client().iter_participants(&tg_chat);
+        if let Some(filter) = filter {
+            participants = participants.filter(filter);
+        }
+        let mut ids = HashSet::new();
+
+        loop {
+            match participants.next().await {
+                Ok(Some(participant)) => {
+                    if keep(&participant.role) {
+                        ids.insert(participant.user.id());
+                    }
+                }
+                Ok(None) => break,
+                Err(e) => {
+                    error!("Failed to fetch the participants of {chat_name}. Error: {e}");
+                    return Err(unavailable(chat_name.to_string()));
+                }
+            }
+        }
+
+        let total = participants.total().await.unwrap_or(ids.len());
+        Ok(Some((ids, total)))
+    }
+}
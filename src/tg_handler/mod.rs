@@ -3,6 +3,7 @@ mod communication;
 mod counter;
 mod handler;
 mod initial_session;
+mod members;
 mod new_session;
 mod whitelist;
 
@@ -1,35 +1,49 @@
+use chrono::{DateTime, Local};
 use eframe::{egui, App, CreationContext, Frame};
 use egui::{
-    Align, Button, CentralPanel, Context, FontData, FontDefinitions, FontFamily, Id, Layout, Modal,
-    Rounding, ScrollArea, Spinner, ThemePreference, TopBottomPanel, ViewportCommand, Visuals,
+    Align, Button, CentralPanel, Context, FontData, FontDefinitions, FontFamily, Id, Key, Layout,
+    Modal, Modifiers, ProgressBar, Rounding, ScrollArea, Spinner, Theme, ThemePreference,
+    TopBottomPanel, Ui, Vec2, ViewportCommand,
 };
 use egui_theme_lerp::ThemeAnimator;
-use log::info;
-use std::collections::{BTreeMap, HashMap};
+use log::{error, info};
+use std::collections::{BTreeMap, HashMap, HashSet, VecDeque};
+use std::fs::File;
+use std::io::BufWriter;
 use std::slice::IterMut;
-use std::sync::atomic::AtomicBool;
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::mpsc::{channel, Receiver, Sender};
 use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
 use strum::IntoEnumIterator;
 use tokio::runtime::Runtime;
 
 use crate::tg_handler::{start_process, NewProcess, ProcessResult, ProcessStart, TGClient};
 use crate::ui_components::processor::{
-    check_version, download_font, AppState, CounterCounts, ParsedChat, ProcessState, TabState,
+    check_version, download_font, AccentColor, AppSettings, AppState, CounterCounts, DateFormat,
+    ParsedChat, ProcessState, TabState, UndoAction,
 };
 use crate::ui_components::tab_ui::{
-    BlacklistData, ChartsData, CounterData, SessionData, UserTableData, WhitelistData,
+    BlacklistData, ChartsData, CounterData, OverlapUser, SessionData, UserTableData, WhitelistData,
 };
 use crate::ui_components::widgets::AnimatedLabel;
-use crate::ui_components::TGKeys;
+use crate::ui_components::{KeyProfiles, TGKeys};
 use crate::utils::{
-    find_session_files, get_api_keys, get_font_data, get_runtime, last_theme, save_theme,
-    theme_hover_text,
+    chat_summary_text, find_session_files, get_font_data, get_key_profiles, get_runtime,
+    get_settings, import_table_data, last_theme, save_settings, save_theme, theme_hover_text,
 };
 
+/// Maximum number of status messages kept in the status history
+const STATUS_HISTORY_LIMIT: usize = 100;
+/// How often the unique user count across all chats is recalculated
+const UNIQUE_USER_INTERVAL: Duration = Duration::from_secs(1);
+
 pub struct MainWindow {
     pub app_state: AppState,
     pub tg_keys: TGKeys,
+    pub key_profiles: KeyProfiles,
+    /// Name of the profile being edited in the API key UI
+    pub key_profile_name: String,
     pub counter: CounterData,
     table: Vec<UserTableData>,
     chart: Vec<ChartsData>,
@@ -51,14 +65,46 @@ pub struct MainWindow {
     pub chart_chat_index: usize,
     pub initial_chart_reset: bool,
     pub cancel_count: Arc<AtomicBool>,
+    /// Whether to stop loading the saved whitelist and blacklist users
+    pub cancel_load: Arc<AtomicBool>,
     pub theme_animator: ThemeAnimator,
     pub runtime: Runtime,
+    pub stream_writer: Option<BufWriter<File>>,
+    pub settings: AppSettings,
+    /// Window size of the last frame, used to detect resizes
+    last_window_size: Option<Vec2>,
+    /// Size requested on a tab switch and when it was requested. Resizes are not saved until it is applied
+    pending_resize: Option<(Vec2, Instant)>,
+    /// When the window size of a tab last changed without being saved yet
+    window_size_changed: Option<Instant>,
+    /// The last whitelist or blacklist change that can be undone
+    pub last_action: Option<UndoAction>,
+    /// The latest status messages and when they were shown, oldest first
+    status_history: VecDeque<(DateTime<Local>, String)>,
+    show_status_history: bool,
+    /// Path of the exported table CSV to import
+    pub table_import_path: String,
+    /// Whether the confirmation to close the app while counting is shown
+    pub show_close_modal: bool,
+    /// Whether the user confirmed closing the app while counting
+    close_confirmed: bool,
+    /// Distinct users across all counted chats and when it was last calculated
+    unique_users: Option<(usize, Instant)>,
+    /// Users found in more than one counted chat. The overlap view is shown while set
+    pub overlap_users: Option<Vec<OverlapUser>>,
 }
 
 impl MainWindow {
     pub fn new(cc: &CreationContext) -> Self {
         let last_theme_light = last_theme().unwrap_or_default();
-        let mut animator = ThemeAnimator::new(Visuals::light(), Visuals::dark());
+        let settings = get_settings();
+        let light_visuals = settings.accent_color.visuals(false);
+        let dark_visuals = settings.accent_color.visuals(true);
+        cc.egui_ctx
+            .set_visuals_of(Theme::Light, light_visuals.clone());
+        cc.egui_ctx
+            .set_visuals_of(Theme::Dark, dark_visuals.clone());
+        let mut animator = ThemeAnimator::new(light_visuals, dark_visuals);
 
         if last_theme_light {
             cc.egui_ctx
@@ -71,9 +117,16 @@ impl MainWindow {
         };
         let (sender, receiver) = channel();
 
+        let start_tab = settings.start_tab;
+        let window_size = settings.window_size(&start_tab);
+        cc.egui_ctx
+            .send_viewport_cmd(ViewportCommand::InnerSize(window_size));
+
         Self {
             app_state: AppState::default(),
             tg_keys: TGKeys::default(),
+            key_profiles: KeyProfiles::default(),
+            key_profile_name: String::new(),
             counter: CounterData::default(),
             // default value with an existing one with default
             table: vec![UserTableData::default()],
@@ -81,7 +134,7 @@ impl MainWindow {
             whitelist: WhitelistData::default(),
             blacklist: BlacklistData::default(),
             session: SessionData::default(),
-            tab_state: TabState::Counter,
+            tab_state: start_tab,
             process_state: ProcessState::Idle,
             tg_sender: sender,
             tg_receiver: receiver,
@@ -96,8 +149,22 @@ impl MainWindow {
             chart_chat_index: 0,
             initial_chart_reset: false,
             cancel_count: Arc::new(AtomicBool::new(false)),
+            cancel_load: Arc::new(AtomicBool::new(false)),
             theme_animator: animator,
             runtime: get_runtime(),
+            stream_writer: None,
+            settings,
+            last_window_size: None,
+            pending_resize: Some((window_size, Instant::now())),
+            window_size_changed: None,
+            last_action: None,
+            status_history: VecDeque::new(),
+            show_status_history: false,
+            table_import_path: String::new(),
+            show_close_modal: false,
+            close_confirmed: false,
+            unique_users: None,
+            overlap_users: None,
         }
     }
 }
@@ -105,25 +172,18 @@ impl MainWindow {
 impl App for MainWindow {
     fn update(&mut self, ctx: &Context, _: &mut Frame) {
         // If asked to close the app, search for any temporary client and if any, logout then close the window
+        // Closing while counting needs to be confirmed first as the progress would be lost
         if ctx.input(|i| i.viewport().close_requested()) {
-            let mut joins = Vec::new();
-            for (_, client) in self.tg_clients.clone() {
-                if client.is_temporary() {
-                    let joiner = self.runtime.spawn(async move {
-                        client.start_process(ProcessStart::SessionLogout).await
-                    });
-                    joins.push(joiner);
-                }
+            if self.is_processing && self.counter.counting() && !self.close_confirmed {
+                ctx.send_viewport_cmd(ViewportCommand::CancelClose);
+                self.show_close_modal = true;
+            } else {
+                self.logout_temporary_clients();
             }
+        }
 
-            while !joins.is_empty() {
-                for (index, join) in joins.iter().enumerate() {
-                    if join.is_finished() {
-                        joins.remove(index);
-                        break;
-                    }
-                }
-            }
+        if self.show_close_modal {
+            self.show_close_modal(ctx);
         }
 
         match self.app_state {
@@ -131,15 +191,26 @@ impl App for MainWindow {
                 ctx.set_pixels_per_point(1.1);
                 self.set_fonts(ctx);
 
+                self.key_profiles = get_key_profiles().unwrap_or_default();
+
                 // If API keys are found, start the main UI otherwise show the UI to input the api keys
-                if get_api_keys().is_some() {
+                if self.key_profiles.active_keys().is_some() {
                     self.app_state = AppState::InitializedUI;
                 } else {
-                    self.app_state = AppState::InputAPIKeys;
+                    self.open_keys_ui();
                 }
             }
             AppState::InputAPIKeys => self.show_tg_keys_ui(ctx),
             AppState::InitializedUI => {
+                self.track_window_size(ctx);
+
+                // Text boxes handle their own undo
+                let undo_pressed = !ctx.wants_keyboard_input()
+                    && ctx.input_mut(|i| i.consume_key(Modifiers::COMMAND, Key::Z));
+                if undo_pressed && !self.is_processing {
+                    self.undo_last_action();
+                }
+
                 TopBottomPanel::top("top_panel")
                     .show_separator_line(false)
                     .show(ctx, |ui| {
@@ -174,6 +245,86 @@ impl App for MainWindow {
                                 save_theme(self.is_light_theme);
                             };
 
+                            ui.menu_button("🎨", |ui| {
+                                for accent in AccentColor::iter() {
+                                    if ui
+                                        .selectable_value(
+                                            &mut self.settings.accent_color,
+                                            accent,
+                                            accent.to_string(),
+                                        )
+                                        .clicked()
+                                    {
+                                        self.apply_accent_color(ctx);
+                                        save_settings(&self.settings);
+                                        ui.close_menu();
+                                    }
+                                }
+                            })
+                            .response
+                            .on_hover_text("Change the accent color of the theme");
+
+                            ui.menu_button("📅", |ui| {
+                                for format in DateFormat::iter() {
+                                    if ui
+                                        .selectable_value(
+                                            &mut self.settings.date_format,
+                                            format,
+                                            format.to_string(),
+                                        )
+                                        .clicked()
+                                    {
+                                        self.apply_display_settings();
+                                        save_settings(&self.settings);
+                                        ui.close_menu();
+                                    }
+                                }
+                            })
+                            .response
+                            .on_hover_text("Change how dates are shown in the table and charts");
+
+                            ui.menu_button("🔤", |ui| {
+                                if ui
+                                    .checkbox(
+                                        &mut self.settings.skip_font_download,
+                                        "Skip font download",
+                                    )
+                                    .on_hover_text("Use the default fonts without downloading the missing CJK and Gentium font files. Some characters may not be shown")
+                                    .changed()
+                                {
+                                    save_settings(&self.settings);
+                                    if !self.settings.skip_font_download {
+                                        self.set_fonts(ctx);
+                                    }
+                                }
+                            })
+                            .response
+                            .on_hover_text("Font settings");
+
+                            ui.menu_button("🔑", |ui| {
+                                let mut selected = None;
+                                for name in self.key_profiles.profiles.keys() {
+                                    if ui
+                                        .selectable_label(*name == self.key_profiles.active, name)
+                                        .clicked()
+                                    {
+                                        selected = Some(name.clone());
+                                    }
+                                }
+                                if let Some(name) = selected {
+                                    self.set_active_key_profile(&name);
+                                    ui.close_menu();
+                                }
+
+                                ui.separator();
+                                if ui.button("Manage Keys").clicked() {
+                                    self.open_keys_ui();
+                                    ui.close_menu();
+                                }
+                            })
+                            .response
+                            .on_hover_text("Switch the API key profile used for connecting and logging in. Already connected sessions keep their keys until the app restarts");
+
                             let hover_position = ui.make_persistent_id("tab_hover");
                             let selected_position = ui.make_persistent_id("tab_selected");
 
@@ -192,9 +343,25 @@ impl App for MainWindow {
                                     (first_val, true),
                                 ));
 
+                                resp.context_menu(|ui| {
+                                    if ui
+                                        .selectable_label(
+                                            self.settings.start_tab == val,
+                                            "Open on startup",
+                                        )
+                                        .on_hover_text("Open this tab when the app starts")
+                                        .clicked()
+                                    {
+                                        self.settings.start_tab = val;
+                                        save_settings(&self.settings);
+                                        ui.close_menu();
+                                    }
+                                });
+
                                 if resp.clicked() {
-                                    let window_size = val.window_size();
+                                    let window_size = self.settings.window_size(&val);
                                     ctx.send_viewport_cmd(ViewportCommand::InnerSize(window_size));
+                                    self.pending_resize = Some((window_size, Instant::now()));
                                     self.tab_state = val
                                 }
                             }
@@ -204,15 +371,71 @@ impl App for MainWindow {
                 TopBottomPanel::bottom("bottom_panel")
                     .show_separator_line(false)
                     .show(ctx, |ui| {
+                        self.record_status();
+                        if self.show_status_history {
+                            self.show_status_history(ui);
+                        }
                         ui.add_space(4.0);
                         let status_text = self.process_state.to_string();
+
+                        // Keep the flood wait countdown updating
+                        if matches!(self.process_state, ProcessState::FloodWait(Some(_))) {
+                            ctx.request_repaint_after(Duration::from_secs(1));
+                        }
+                        let load_progress = match self.process_state {
+                            ProcessState::LoadingWhitelistedUsers(done, total)
+                            | ProcessState::LoadingBlacklistedUsers(done, total)
+                                if total > 0 =>
+                            {
+                                Some(done as f32 / total as f32)
+                            }
+                            _ => None,
+                        };
                         ui.horizontal(|ui| {
+                            if ui
+                                .selectable_label(self.show_status_history, "📜")
+                                .on_hover_text("Show the status history")
+                                .clicked()
+                            {
+                                self.show_status_history = !self.show_status_history;
+                            }
                             ui.label(status_text);
-                            if self.is_processing {
-                                ui.with_layout(Layout::right_to_left(Align::Center), |ui| {
+                            if let ProcessState::DataExported(location, _) = &self.process_state {
+                                if ui
+                                    .button("Open folder")
+                                    .on_hover_text("Open the folder with the exported files")
+                                    .clicked()
+                                {
+                                    if let Err(e) = open::that(location) {
+                                        error!("Failed to open the export folder. Error: {e}");
+                                    }
+                                }
+                            }
+                            if let Some(action) = &self.last_action {
+                                let undo_button = ui
+                                    .add_enabled(!self.is_processing, Button::new("Undo"))
+                                    .on_hover_text(format!(
+                                        "Undo {}. Also usable with CTRL + Z",
+                                        action.description()
+                                    ));
+                                if undo_button.clicked() {
+                                    self.undo_last_action();
+                                }
+                            }
+                            ui.with_layout(Layout::right_to_left(Align::Center), |ui| {
+                                if self.is_processing {
                                     ui.add(Spinner::new());
-                                });
-                            };
+                                    if let Some(progress) = load_progress {
+                                        self.show_load_progress(ui, progress);
+                                    }
+                                }
+                                if self.counter.total_chats() > 1 {
+                                    ui.label(format!("Unique Users: {}", self.unique_users()))
+                                        .on_hover_text(
+                                            "Number of distinct users across all counted chats",
+                                        );
+                                }
+                            });
                         });
                         ui.add_space(0.5);
                     });
@@ -336,6 +559,65 @@ impl App for MainWindow {
 }
 
 impl MainWindow {
+    /// Logs out of all temporary sessions and waits for them to finish
+    fn logout_temporary_clients(&mut self) {
+        let mut joins = Vec::new();
+        for (_, client) in self.tg_clients.clone() {
+            if client.is_temporary() {
+                let joiner = self
+                    .runtime
+                    .spawn(async move { client.start_process(ProcessStart::SessionLogout).await });
+                joins.push(joiner);
+            }
+        }
+
+        while !joins.is_empty() {
+            for (index, join) in joins.iter().enumerate() {
+                if join.is_finished() {
+                    joins.remove(index);
+                    break;
+                }
+            }
+        }
+    }
+
+    /// Confirmation modal before closing the app while a chat is being counted
+    fn show_close_modal(&mut self, ctx: &Context) {
+        let modal = Modal::new(Id::new("close_modal")).show(ctx, |ui| {
+            ui.set_width(250.0);
+            ui.vertical_centered(|ui| {
+                ui.heading("Close while counting?");
+            });
+            ui.add_space(5.0);
+            ui.label("Counting is still in progress. All progress of this session will be lost.");
+            ui.add_space(5.0);
+
+            ui.horizontal(|ui| {
+                let button_width = ui.available_width() / 2.0;
+
+                if ui
+                    .add_sized([button_width - 5.0, 15.0], Button::new("Close"))
+                    .clicked()
+                {
+                    self.show_close_modal = false;
+                    self.close_confirmed = true;
+                    ui.ctx().send_viewport_cmd(ViewportCommand::Close);
+                }
+
+                if ui
+                    .add_sized([button_width - 5.0, 15.0], Button::new("Keep Open"))
+                    .clicked()
+                {
+                    self.show_close_modal = false;
+                }
+            });
+        });
+
+        if modal.should_close() {
+            self.show_close_modal = false;
+        }
+    }
+
     pub fn clear_overlap(&mut self, parsed: &HashMap<String, ParsedChat>) {
         self.counter_chat_index = 0;
         self.table_chat_index = 0;
@@ -349,8 +631,198 @@ impl MainWindow {
             }
         }
     }
+
+    /// Remembers the window size of the current tab when the user resizes the window
+    fn track_window_size(&mut self, ctx: &Context) {
+        let Some(size) = ctx
+            .input(|i| i.viewport().inner_rect)
+            .map(|rect| rect.size())
+        else {
+            return;
+        };
+
+        let previous_size = self.last_window_size.replace(size);
+        let resized = previous_size.is_some_and(|previous| previous != size);
+
+        if let Some((target, requested_at)) = self.pending_resize {
+            let reached = (size - target).length() < 1.0;
+            if reached || requested_at.elapsed() > Duration::from_secs(1) {
+                self.pending_resize = None;
+            }
+            return;
+        }
+
+        if resized {
+            self.settings.set_window_size(&self.tab_state, size);
+            self.window_size_changed = Some(Instant::now());
+        }
+
+        // Wait for the resizing to stop before writing to the disk
+        if let Some(changed_at) = self.window_size_changed {
+            if changed_at.elapsed() > Duration::from_millis(500) {
+                self.window_size_changed = None;
+                save_settings(&self.settings);
+            } else {
+                ctx.request_repaint_after(Duration::from_millis(500));
+            }
+        }
+    }
+
+    /// Wipe all counted data and start fresh
+    pub fn reset_all(&mut self) {
+        info!("Resetting all counted data");
+        self.clear_blacklist_undo();
+        self.reset_counts();
+        self.reset_table();
+        self.reset_chart();
+        self.counter.set_bar_percentage(0.0);
+        self.counter_chat_index = 0;
+        self.table_chat_index = 0;
+        self.chart_chat_index = 0;
+        self.process_state = ProcessState::AllDataReset;
+    }
+
+    /// Adds the current status to the history if it changed since the last frame
+    fn record_status(&mut self) {
+        let text = self.process_state.history_text();
+        if self
+            .status_history
+            .back()
+            .is_some_and(|(_, last_text)| *last_text == text)
+        {
+            return;
+        }
+
+        self.status_history.push_back((Local::now(), text));
+        if self.status_history.len() > STATUS_HISTORY_LIMIT {
+            self.status_history.pop_front();
+        }
+    }
+
+    /// Shows the latest status messages with the time they were shown
+    fn show_status_history(&mut self, ui: &mut Ui) {
+        ui.add_space(4.0);
+        ScrollArea::vertical()
+            .max_height(120.0)
+            .auto_shrink([false, true])
+            .stick_to_bottom(true)
+            .show(ui, |ui| {
+                for (time, text) in &self.status_history {
+                    ui.label(format!("[{}] {text}", time.format("%H:%M:%S")));
+                }
+            });
+        ui.separator();
+    }
+
+    /// Reverts the last whitelist or blacklist change
+    fn undo_last_action(&mut self) {
+        let Some(action) = self.last_action.take() else {
+            return;
+        };
+
+        info!("Undoing {}", action.description());
+        match action {
+            UndoAction::Whitelisted(ids) => {
+                self.whitelist.remove_users(&ids);
+                for table in self.table_all() {
+                    table.remove_whitelist(&ids);
+                }
+                self.chart().reset_saved_bars();
+            }
+            UndoAction::Blacklisted {
+                ids,
+                table_rows,
+                chart_users,
+            } => {
+                self.blacklist.remove_users(&ids);
+                for (table, rows) in self.table_all().zip(table_rows) {
+                    table.restore_rows(rows);
+                }
+                for (chart, users) in self.chart_all().zip(chart_users) {
+                    chart.restore_blacklisted(users);
+                }
+            }
+            UndoAction::WhitelistRemoved(users) => {
+                let ids: Vec<i64> = users.iter().map(|user| user.id).collect();
+                for user in users {
+                    self.whitelist.add_to_whitelist(
+                        user.name,
+                        user.username,
+                        user.id,
+                        user.belongs_to,
+                        user.seen_by,
+                    );
+                }
+                self.whitelist.save_whitelisted_users(true);
+                for table in self.table_all() {
+                    table.set_as_whitelisted(&ids);
+                }
+                for chart in self.chart_all() {
+                    chart.reset_saved_bars();
+                }
+            }
+            UndoAction::BlacklistRemoved(users) => {
+                for user in users {
+                    self.blacklist.add_to_blacklist(
+                        user.name,
+                        user.username,
+                        user.id,
+                        user.belongs_to,
+                        user.seen_by,
+                    );
+                }
+                self.blacklist.save_blacklisted_users(true);
+            }
+        }
+        self.process_state = ProcessState::ActionUndone;
+    }
+
+    /// Forgets the last blacklist action as the removed data no longer matches the counted chats
+    pub fn clear_blacklist_undo(&mut self) {
+        if matches!(self.last_action, Some(UndoAction::Blacklisted { .. })) {
+            self.last_action = None;
+        }
+    }
+
+    /// Show the saved user loading progress with a button to stop loading
+    fn show_load_progress(&mut self, ui: &mut Ui, progress: f32) {
+        let cancelled = self.cancel_load.load(Ordering::Acquire);
+        if ui
+            .add_enabled(!cancelled, Button::new("Cancel"))
+            .on_hover_text("Stop loading and continue with the users loaded so far. Users that were not loaded stay in the saved list and are loaded again on the next start")
+            .clicked()
+        {
+            self.cancel_load.store(true, Ordering::Release);
+        }
+        ui.add(
+            ProgressBar::new(progress)
+                .desired_width(150.0)
+                .show_percentage(),
+        );
+    }
+
+    /// Distinct users across all counted chats, recalculated at most once per interval
+    fn unique_users(&mut self) -> usize {
+        if let Some((total, updated)) = self.unique_users {
+            if updated.elapsed() < UNIQUE_USER_INTERVAL {
+                return total;
+            }
+        }
+
+        let user_ids: HashSet<i64> = self
+            .table
+            .iter()
+            .flat_map(UserTableData::user_ids)
+            .copied()
+            .collect();
+        let total = user_ids.len();
+        self.unique_users = Some((total, Instant::now()));
+        total
+    }
+
     pub fn reset_table(&mut self) {
         self.table = vec![UserTableData::default()];
+        self.apply_display_settings();
     }
 
     pub fn reset_counts(&mut self) {
@@ -362,6 +834,7 @@ impl MainWindow {
         chart.reset_chart();
 
         self.chart = vec![chart];
+        self.apply_display_settings();
     }
 
     /// Only called once after the Start button is pressed for the first time
@@ -388,6 +861,108 @@ impl MainWindow {
         while self.counter.counts.len() != amount {
             self.counter.counts.push(CounterCounts::default());
         }
+        self.apply_display_settings();
+    }
+
+    /// Applies the skip bots, skip admins and the date format setting to the tables and charts
+    /// of all chats
+    pub fn apply_display_settings(&mut self) {
+        for (table, chart) in self.table.iter_mut().zip(self.chart.iter_mut()) {
+            apply_display_settings_to(&self.settings, table, chart);
+        }
+    }
+
+    /// Combines the data of two counted chats into a new chat entry
+    pub fn merge_chats(&mut self, first: usize, second: usize) {
+        let chat_list = self.counter.get_chat_list();
+        let merged_name = format!("{} + {}", chat_list[first], chat_list[second]);
+
+        if self.counter.contains_chat(&merged_name) {
+            self.process_state = ProcessState::MergedChatExists(merged_name);
+            return;
+        }
+
+        info!("Merging {} and {}", chat_list[first], chat_list[second]);
+        self.clear_blacklist_undo();
+
+        let mut table = UserTableData::default();
+        let mut chart = ChartsData::default();
+        chart.reset_chart();
+        apply_display_settings_to(&self.settings, &mut table, &mut chart);
+
+        table.merge_from(&self.table[first]);
+        table.merge_from(&self.table[second]);
+        chart.merge_from(&self.chart[first]);
+        chart.merge_from(&self.chart[second]);
+
+        let mut counts = self.counter.counts[first].clone();
+        counts.merge_from(&self.counter.counts[second]);
+        counts.set_total_user(table.get_total_users_full() as i32);
+
+        self.counter.add_to_chat(merged_name.clone());
+        self.counter.counts.push(counts);
+        self.table.push(table);
+        self.chart.push(chart);
+
+        self.process_state = ProcessState::ChatsMerged(merged_name);
+    }
+
+    /// Apply the selected accent color to both the light and the dark theme
+    fn apply_accent_color(&mut self, ctx: &Context) {
+        let light_visuals = self.settings.accent_color.visuals(false);
+        let dark_visuals = self.settings.accent_color.visuals(true);
+
+        // The animator switches themes by replacing the visuals of the active egui theme
+        if self.is_light_theme {
+            ctx.set_visuals(light_visuals.clone());
+        } else {
+            ctx.set_visuals(dark_visuals.clone());
+        }
+        self.theme_animator.theme_1 = light_visuals;
+        self.theme_animator.theme_2 = dark_visuals;
+    }
+
+    /// Import a previously exported table CSV as a new chat. Only the table is available for it
+    /// as the export does not contain the per-date data the charts need
+    pub fn import_table(&mut self) {
+        let (chat_name, rows) = match import_table_data(&self.table_import_path) {
+            Ok(data) => data,
+            Err(e) => {
+                error!("Failed to import the table. Error: {e}");
+                self.process_state = ProcessState::TableImportFailed(e.to_string());
+                return;
+            }
+        };
+
+        if self.counter.contains_chat(&chat_name) {
+            self.process_state = ProcessState::MergedChatExists(chat_name);
+            return;
+        }
+
+        info!("Importing {} rows into {chat_name}", rows.len());
+        self.clear_blacklist_undo();
+
+        let total_rows = rows.len();
+        let mut table = UserTableData::default();
+        let mut chart = ChartsData::default();
+        chart.reset_chart();
+        apply_display_settings_to(&self.settings, &mut table, &mut chart);
+        let counts = table.import_rows(rows, self.settings.char_count);
+
+        // Remove the placeholder data that exists before any chat is added
+        if self.counter.total_chats() == 0 {
+            self.table.clear();
+            self.chart.clear();
+            self.counter.counts.clear();
+        }
+
+        self.counter.add_to_chat(chat_name.clone());
+        self.counter.counts.push(counts);
+        self.table.push(table);
+        self.chart.push(chart);
+        self.table_chat_index = self.table.len() - 1;
+
+        self.process_state = ProcessState::TableImported(chat_name, total_rows);
     }
 
     /// Get all the added session names
@@ -396,7 +971,7 @@ impl MainWindow {
     }
 
     /// Set the fonts for egui to use or download them if does not exist
-    pub fn set_fonts(&self, ctx: &Context) {
+    pub fn set_fonts(&mut self, ctx: &Context) {
         let font_data = get_font_data();
 
         if let Some((cjk, gentium)) = font_data {
@@ -419,6 +994,9 @@ impl MainWindow {
                 .extend(["NotoSansCJK".to_owned(), "GentiumBookPlus".to_owned()]);
 
             ctx.set_fonts(font_definitions);
+        } else if self.settings.skip_font_download {
+            info!("Could not find font data. Using the default fonts as downloading is disabled");
+            self.process_state = ProcessState::FontsMissing;
         } else {
             info!("Could not find font data. Starting download");
             let ctx_clone = ctx.clone();
@@ -429,6 +1007,34 @@ impl MainWindow {
         }
     }
 
+    /// Whether the chat at the given selector index was counted without per-user stats
+    pub fn is_count_only(&self, index: usize) -> bool {
+        let index = if self.counter.total_chats() > 1 {
+            index
+        } else {
+            0
+        };
+        self.counter
+            .counts
+            .get(index)
+            .is_some_and(|count| count.count_only)
+    }
+
+    /// Text summary of the statistics of the chat at the given selector index
+    pub fn chat_summary(&self, index: usize) -> String {
+        let index = if self.counter.total_chats() > 1 {
+            index
+        } else {
+            0
+        };
+        chat_summary_text(
+            &self.counter.selected_chat_name(index),
+            &self.counter.counts[index],
+            &self.table[index],
+            &self.chart[index],
+        )
+    }
+
     /// Return the currently selected table data as mutable
     pub fn table(&mut self) -> &mut UserTableData {
         if self.counter.total_chats() > 1 {
@@ -492,11 +1098,28 @@ impl MainWindow {
         &mut self.counter.counts[ongoing]
     }
 
-    pub fn chart_all(&mut self) -> IterMut<ChartsData> {
+    pub fn chart_all(&mut self) -> IterMut<'_, ChartsData> {
         self.chart.iter_mut()
     }
 
-    pub fn table_all(&mut self) -> IterMut<UserTableData> {
+    pub fn table_all(&mut self) -> IterMut<'_, UserTableData> {
         self.table.iter_mut()
     }
 }
+
+/// Applies the display related settings to the table and the chart of a chat
+fn apply_display_settings_to(
+    settings: &AppSettings,
+    table: &mut UserTableData,
+    chart: &mut ChartsData,
+) {
+    table.set_skip_bots(settings.skip_bots);
+    table.set_skip_admins(settings.skip_admins);
+    table.set_date_format(settings.date_format);
+    table.set_compact_dates(settings.compact_dates);
+
+    chart.set_skip_bots(settings.skip_bots);
+    chart.set_skip_admins(settings.skip_admins);
+    // The saved bars have the dates in their names
+    chart.reset_saved_bars();
+}
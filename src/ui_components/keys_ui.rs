@@ -1,17 +1,74 @@
-use eframe::egui::{vec2, Align, Button, CentralPanel, Context, Grid, Label, Layout, TextEdit};
+use eframe::egui::{
+    vec2, Align, Button, CentralPanel, ComboBox, Context, Grid, Label, Layout, TextEdit,
+};
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 
 use crate::ui_components::processor::AppState;
 use crate::ui_components::MainWindow;
-use crate::utils::save_api_keys;
+use crate::utils::save_key_profiles;
 
-#[derive(Deserialize, Serialize, Default)]
+/// Name of the profile the keys of the old single key file are moved to
+pub const DEFAULT_KEY_PROFILE: &str = "Default";
+
+#[derive(Deserialize, Serialize, Default, Clone)]
 pub struct TGKeys {
     pub api_id: String,
     pub api_hash: String,
 }
 
+/// Named API key pairs and the name of the one used for connecting to the sessions
+#[derive(Deserialize, Serialize)]
+pub struct KeyProfiles {
+    pub active: String,
+    pub profiles: BTreeMap<String, TGKeys>,
+}
+
+impl Default for KeyProfiles {
+    fn default() -> Self {
+        Self {
+            active: DEFAULT_KEY_PROFILE.to_string(),
+            profiles: BTreeMap::new(),
+        }
+    }
+}
+
+impl KeyProfiles {
+    /// Wraps a single key pair in the default profile
+    pub fn from_keys(keys: TGKeys) -> Self {
+        Self {
+            active: DEFAULT_KEY_PROFILE.to_string(),
+            profiles: BTreeMap::from([(DEFAULT_KEY_PROFILE.to_string(), keys)]),
+        }
+    }
+
+    /// The keys of the active profile if they are not empty
+    pub fn active_keys(&self) -> Option<&TGKeys> {
+        self.profiles
+            .get(&self.active)
+            .filter(|keys| !keys.api_id.is_empty() && !keys.api_hash.is_empty())
+    }
+}
+
 impl MainWindow {
+    /// Opens the API key UI with the keys of the active profile filled in
+    pub fn open_keys_ui(&mut self) {
+        self.key_profile_name.clone_from(&self.key_profiles.active);
+        self.tg_keys = self
+            .key_profiles
+            .profiles
+            .get(&self.key_profiles.active)
+            .cloned()
+            .unwrap_or_default();
+        self.app_state = AppState::InputAPIKeys;
+    }
+
+    /// Makes the given profile the one used for new connections
+    pub fn set_active_key_profile(&mut self, name: &str) {
+        self.key_profiles.active = name.to_string();
+        save_key_profiles(&self.key_profiles);
+    }
+
     pub fn show_tg_keys_ui(&mut self, ctx: &Context) {
         CentralPanel::default().show(ctx, |ui| {
             let tg_link = "https://my.telegram.org/";
@@ -34,6 +91,50 @@ impl MainWindow {
                     });
                     ui.end_row();
 
+                    if !self.key_profiles.profiles.is_empty() {
+                        ui.with_layout(Layout::right_to_left(Align::Center), |ui| {
+                            ui.add(Label::new("Saved Profiles:"));
+                        });
+                        ui.with_layout(Layout::left_to_right(Align::Center), |ui| {
+                            let mut selected = None;
+                            ComboBox::from_id_salt("Key Profile Box")
+                                .selected_text(&self.key_profile_name)
+                                .show_ui(ui, |ui| {
+                                    for name in self.key_profiles.profiles.keys() {
+                                        if ui
+                                            .selectable_label(
+                                                *name == self.key_profile_name,
+                                                name,
+                                            )
+                                            .clicked()
+                                        {
+                                            selected = Some(name.clone());
+                                        }
+                                    }
+                                })
+                                .response
+                                .on_hover_text("Load the keys of a saved profile to edit them");
+
+                            if let Some(name) = selected {
+                                self.tg_keys = self.key_profiles.profiles[&name].clone();
+                                self.key_profile_name = name;
+                            }
+                        });
+                        ui.end_row();
+                    }
+
+                    ui.with_layout(Layout::right_to_left(Align::Center), |ui| {
+                        ui.add(Label::new("Profile Name:"));
+                    });
+                    ui.with_layout(Layout::left_to_right(Align::Center), |ui| {
+                        ui.add(
+                            TextEdit::singleline(&mut self.key_profile_name)
+                                .hint_text(DEFAULT_KEY_PROFILE)
+                                .min_size(ui.available_size()),
+                        );
+                    });
+                    ui.end_row();
+
                     ui.with_layout(Layout::right_to_left(Align::Center), |ui| {
                         ui.add(Label::new("API ID:"));
                     });
@@ -60,15 +161,58 @@ impl MainWindow {
                 });
             ui.add_space(20.0);
             ui.vertical_centered(|ui| {
-                if ui
-                    .add_sized(vec2(80.0, 40.0), Button::new("Save Keys"))
-                    .clicked()
-                    && !self.tg_keys.api_hash.is_empty()
-                    && !self.tg_keys.api_id.is_empty()
-                {
-                    save_api_keys(&self.tg_keys);
-                    self.app_state = AppState::InitializedUI;
-                }
+                ui.horizontal(|ui| {
+                    let has_keys = self.key_profiles.active_keys().is_some();
+                    let profile_name = self.key_profile_name.trim().to_string();
+                    let saved_profile = self.key_profiles.profiles.contains_key(&profile_name);
+
+                    // Center the buttons
+                    let total_width = if has_keys { 3.0 } else { 1.0 } * 85.0;
+                    ui.add_space((ui.available_width() - total_width) / 2.0);
+
+                    if ui
+                        .add_sized(vec2(80.0, 40.0), Button::new("Save Keys"))
+                        .on_hover_text("Save the keys under the profile name and use them")
+                        .clicked()
+                        && !self.tg_keys.api_hash.is_empty()
+                        && !self.tg_keys.api_id.is_empty()
+                    {
+                        let profile_name = if profile_name.is_empty() {
+                            DEFAULT_KEY_PROFILE.to_string()
+                        } else {
+                            profile_name.clone()
+                        };
+                        self.key_profiles
+                            .profiles
+                            .insert(profile_name.clone(), self.tg_keys.clone());
+                        self.set_active_key_profile(&profile_name);
+                        self.app_state = AppState::InitializedUI;
+                    }
+
+                    if !has_keys {
+                        return;
+                    }
+
+                    // The active profile is needed to connect to the sessions
+                    let delete_enabled =
+                        saved_profile && profile_name != self.key_profiles.active;
+                    if ui
+                        .add_enabled(delete_enabled, Button::new("Delete").min_size(vec2(80.0, 40.0)))
+                        .on_hover_text("Delete this profile. The active profile cannot be deleted")
+                        .clicked()
+                    {
+                        self.key_profiles.profiles.remove(&profile_name);
+                        save_key_profiles(&self.key_profiles);
+                        self.open_keys_ui();
+                    }
+
+                    if ui
+                        .add_sized(vec2(80.0, 40.0), Button::new("Cancel"))
+                        .clicked()
+                    {
+                        self.app_state = AppState::InitializedUI;
+                    }
+                });
             });
         });
     }
@@ -0,0 +1,243 @@
+use chrono::NaiveDateTime;
+use eframe::egui::{vec2, Color32, Vec2, Visuals};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fmt::{self, Display};
+use std::time::Duration;
+use strum_macros::EnumIter;
+
+use crate::ui_components::processor::{CharCountConfig, TabState};
+
+/// User preferences that are saved between app launches
+#[derive(Default, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AppSettings {
+    pub char_count: CharCountConfig,
+    pub csv_delimiter: CsvDelimiter,
+    /// Whether identifying user details are replaced with pseudonyms in the exported files
+    pub anonymize_export: bool,
+    /// Whether to export the table of each chat once its counting finishes
+    pub auto_save: bool,
+    /// Whether bot users are excluded from the tables and charts
+    pub skip_bots: bool,
+    /// Whether the admins of a chat are excluded from its table and chart once they are fetched
+    pub skip_admins: bool,
+    /// Whether dates far in the past are merged by month in the User Table to limit memory usage
+    pub compact_dates: bool,
+    /// Whether the chart bounds are reset whenever new data is added while counting
+    pub auto_fit_chart: bool,
+    /// How often the cached chart bars are rebuilt while counting
+    pub chart_refresh: ChartRefresh,
+    /// Accent color applied on top of both the light and the dark theme
+    pub accent_color: AccentColor,
+    /// How dates are shown in the User Table and the Charts
+    pub date_format: DateFormat,
+    /// The tab that is open when the app starts
+    pub start_tab: TabState,
+    /// Whether the missing font files are left as is instead of being downloaded
+    pub skip_font_download: bool,
+    /// Last window size chosen by the user for each tab, keyed by the tab name
+    window_sizes: HashMap<String, [f32; 2]>,
+    /// Notes written by the user about each counted chat, keyed by the chat name
+    chat_notes: HashMap<String, String>,
+}
+
+impl AppSettings {
+    /// The window size saved for the tab if the user has resized it before
+    pub fn saved_window_size(&self, tab: &TabState) -> Option<Vec2> {
+        self.window_sizes
+            .get(&tab.to_string())
+            .map(|[width, height]| vec2(*width, *height))
+    }
+
+    /// The window size to use for the tab, falling back to the default size
+    pub fn window_size(&self, tab: &TabState) -> Vec2 {
+        self.saved_window_size(tab)
+            .unwrap_or_else(|| tab.window_size())
+    }
+
+    pub fn set_window_size(&mut self, tab: &TabState, size: Vec2) {
+        self.window_sizes.insert(tab.to_string(), [size.x, size.y]);
+    }
+
+    pub fn chat_note(&self, chat_name: &str) -> String {
+        self.chat_notes.get(chat_name).cloned().unwrap_or_default()
+    }
+
+    /// Sets the note of the chat. An empty note removes it
+    pub fn set_chat_note(&mut self, chat_name: &str, note: String) {
+        if note.trim().is_empty() {
+            self.chat_notes.remove(chat_name);
+        } else {
+            self.chat_notes.insert(chat_name.to_string(), note);
+        }
+    }
+}
+
+/// Minimum time in milliseconds between two rebuilds of the chart bars while new messages are
+/// being counted
+#[derive(Clone, Copy, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct ChartRefresh(pub u32);
+
+impl Default for ChartRefresh {
+    fn default() -> Self {
+        Self(500)
+    }
+}
+
+impl ChartRefresh {
+    pub fn interval(self) -> Duration {
+        Duration::from_millis(u64::from(self.0))
+    }
+}
+
+/// The delimiter used to separate the fields of the exported CSV files
+#[derive(Default, Clone, Copy, PartialEq, Eq, EnumIter, Serialize, Deserialize)]
+pub enum CsvDelimiter {
+    #[default]
+    Comma,
+    Semicolon,
+    Tab,
+}
+
+impl Display for CsvDelimiter {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CsvDelimiter::Comma => write!(f, "Comma"),
+            CsvDelimiter::Semicolon => write!(f, "Semicolon"),
+            CsvDelimiter::Tab => write!(f, "Tab"),
+        }
+    }
+}
+
+impl CsvDelimiter {
+    pub fn as_byte(self) -> u8 {
+        match self {
+            CsvDelimiter::Comma => b',',
+            CsvDelimiter::Semicolon => b';',
+            CsvDelimiter::Tab => b'\t',
+        }
+    }
+}
+
+/// Presets for the selection and hyperlink colors of the theme
+#[derive(Default, Clone, Copy, PartialEq, Eq, EnumIter, Serialize, Deserialize)]
+pub enum AccentColor {
+    #[default]
+    Default,
+    Green,
+    Purple,
+    Orange,
+    Red,
+}
+
+impl Display for AccentColor {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AccentColor::Default => write!(f, "Default"),
+            AccentColor::Green => write!(f, "Green"),
+            AccentColor::Purple => write!(f, "Purple"),
+            AccentColor::Orange => write!(f, "Orange"),
+            AccentColor::Red => write!(f, "Red"),
+        }
+    }
+}
+
+impl AccentColor {
+    /// The selection and the hyperlink color for a light or a dark theme
+    fn colors(self, dark_mode: bool) -> Option<(Color32, Color32)> {
+        let colors = match (self, dark_mode) {
+            (AccentColor::Default, _) => return None,
+            (AccentColor::Green, true) => ((30, 110, 60), (110, 210, 140)),
+            (AccentColor::Green, false) => ((150, 220, 170), (20, 120, 50)),
+            (AccentColor::Purple, true) => ((90, 60, 140), (180, 150, 255)),
+            (AccentColor::Purple, false) => ((200, 180, 240), (110, 60, 180)),
+            (AccentColor::Orange, true) => ((140, 80, 20), (255, 170, 90)),
+            (AccentColor::Orange, false) => ((255, 200, 140), (180, 90, 0)),
+            (AccentColor::Red, true) => ((130, 40, 50), (255, 130, 130)),
+            (AccentColor::Red, false) => ((250, 170, 170), (180, 30, 40)),
+        };
+        let ((sr, sg, sb), (hr, hg, hb)) = colors;
+        Some((Color32::from_rgb(sr, sg, sb), Color32::from_rgb(hr, hg, hb)))
+    }
+
+    /// Light or dark visuals with this accent color applied
+    pub fn visuals(self, dark_mode: bool) -> Visuals {
+        let mut visuals = if dark_mode {
+            Visuals::dark()
+        } else {
+            Visuals::light()
+        };
+
+        if let Some((selection, hyperlink)) = self.colors(dark_mode) {
+            visuals.selection.bg_fill = selection;
+            visuals.hyperlink_color = hyperlink;
+        }
+        visuals
+    }
+}
+
+/// Presets for how dates are shown in the UI
+#[derive(Default, Clone, Copy, PartialEq, Eq, EnumIter, Serialize, Deserialize)]
+pub enum DateFormat {
+    #[default]
+    IsoTime,
+    Iso,
+    UsTime,
+    Us,
+    EuTime,
+    Eu,
+}
+
+impl Display for DateFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DateFormat::IsoTime => write!(f, "ISO with time"),
+            DateFormat::Iso => write!(f, "ISO"),
+            DateFormat::UsTime => write!(f, "US with time"),
+            DateFormat::Us => write!(f, "US"),
+            DateFormat::EuTime => write!(f, "EU with time"),
+            DateFormat::Eu => write!(f, "EU"),
+        }
+    }
+}
+
+impl DateFormat {
+    /// The format string of the date part
+    pub fn date_pattern(self) -> &'static str {
+        match self {
+            DateFormat::IsoTime | DateFormat::Iso => "%Y-%m-%d",
+            DateFormat::UsTime | DateFormat::Us => "%m/%d/%Y",
+            DateFormat::EuTime | DateFormat::Eu => "%d.%m.%Y",
+        }
+    }
+
+    /// Whether the time is shown after the date
+    pub fn with_time(self) -> bool {
+        matches!(
+            self,
+            DateFormat::IsoTime | DateFormat::UsTime | DateFormat::EuTime
+        )
+    }
+
+    /// Formats only the date part of the given time
+    pub fn format_date(self, time: &NaiveDateTime) -> String {
+        time.format(self.date_pattern()).to_string()
+    }
+
+    /// Formats the given time, including the time of the day if the format has it
+    pub fn format_datetime(self, time: &NaiveDateTime) -> String {
+        if self.with_time() {
+            self.format_time(time)
+        } else {
+            self.format_date(time)
+        }
+    }
+
+    /// Formats the given time with the time of the day, regardless of the format
+    pub fn format_time(self, time: &NaiveDateTime) -> String {
+        time.format(&format!("{} %H:%M:%S", self.date_pattern()))
+            .to_string()
+    }
+}
@@ -0,0 +1,149 @@
+use serde::{Deserialize, Serialize};
+use std::fmt::{self, Display};
+use strum_macros::EnumIter;
+use unicode_segmentation::UnicodeSegmentation;
+
+/// How the characters of a message are counted
+#[derive(Default, Clone, Copy, PartialEq, Eq, EnumIter, Serialize, Deserialize)]
+pub enum CharCountMode {
+    /// Length of the message in bytes
+    Bytes,
+    /// Number of Unicode scalar values
+    #[default]
+    Chars,
+    /// Number of user-perceived characters
+    Graphemes,
+}
+
+impl Display for CharCountMode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CharCountMode::Bytes => write!(f, "Bytes"),
+            CharCountMode::Chars => write!(f, "Characters"),
+            CharCountMode::Graphemes => write!(f, "Graphemes"),
+        }
+    }
+}
+
+/// The selected char counting mode and whether whitespace is excluded
+#[derive(Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CharCountConfig {
+    pub mode: CharCountMode,
+    pub exclude_whitespace: bool,
+}
+
+/// Char count of a message in every supported mode so the mode can be switched without recounting
+#[derive(Default, Clone, Copy)]
+pub struct CharCounts {
+    bytes: u32,
+    chars: u32,
+    graphemes: u32,
+    bytes_no_space: u32,
+    chars_no_space: u32,
+    graphemes_no_space: u32,
+}
+
+impl CharCounts {
+    pub fn from_text(text: &str) -> Self {
+        let mut counts = CharCounts::default();
+
+        for grapheme in text.graphemes(true) {
+            let bytes = grapheme.len() as u32;
+            let chars = grapheme.chars().count() as u32;
+
+            counts.bytes += bytes;
+            counts.chars += chars;
+            counts.graphemes += 1;
+
+            if !grapheme.chars().all(char::is_whitespace) {
+                counts.bytes_no_space += bytes;
+                counts.chars_no_space += chars;
+                counts.graphemes_no_space += 1;
+            }
+        }
+        counts
+    }
+
+    /// Char counts with the same value in every mode, used when only the total is known
+    pub fn uniform(count: u32) -> Self {
+        CharCounts {
+            bytes: count,
+            chars: count,
+            graphemes: count,
+            bytes_no_space: count,
+            chars_no_space: count,
+            graphemes_no_space: count,
+        }
+    }
+
+    /// Add the counts of another message
+    pub fn add(&mut self, other: CharCounts) {
+        self.bytes += other.bytes;
+        self.chars += other.chars;
+        self.graphemes += other.graphemes;
+        self.bytes_no_space += other.bytes_no_space;
+        self.chars_no_space += other.chars_no_space;
+        self.graphemes_no_space += other.graphemes_no_space;
+    }
+
+    /// The char count based on the given config
+    pub fn get(&self, config: CharCountConfig) -> u32 {
+        match (config.mode, config.exclude_whitespace) {
+            (CharCountMode::Bytes, false) => self.bytes,
+            (CharCountMode::Chars, false) => self.chars,
+            (CharCountMode::Graphemes, false) => self.graphemes,
+            (CharCountMode::Bytes, true) => self.bytes_no_space,
+            (CharCountMode::Chars, true) => self.chars_no_space,
+            (CharCountMode::Graphemes, true) => self.graphemes_no_space,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Asserts the count of every mode in the order bytes, chars, graphemes
+    fn assert_counts(text: &str, with_space: [u32; 3], without_space: [u32; 3]) {
+        let counts = CharCounts::from_text(text);
+        let modes = [
+            CharCountMode::Bytes,
+            CharCountMode::Chars,
+            CharCountMode::Graphemes,
+        ];
+
+        for (index, mode) in modes.into_iter().enumerate() {
+            let config = CharCountConfig {
+                mode,
+                exclude_whitespace: false,
+            };
+            assert_eq!(counts.get(config), with_space[index], "{mode} of {text}");
+
+            let config = CharCountConfig {
+                mode,
+                exclude_whitespace: true,
+            };
+            assert_eq!(
+                counts.get(config),
+                without_space[index],
+                "{mode} without whitespace of {text}"
+            );
+        }
+    }
+
+    #[test]
+    fn counts_cyrillic() {
+        assert_counts("Привет мир", [19, 10, 10], [18, 9, 9]);
+    }
+
+    #[test]
+    fn counts_cjk() {
+        assert_counts("你好 世界", [13, 5, 5], [12, 4, 4]);
+    }
+
+    #[test]
+    fn counts_emoji_with_modifier() {
+        // The skin tone modifier is a separate char but the same grapheme
+        assert_counts("👍🏽 ok", [11, 5, 4], [10, 4, 3]);
+    }
+}
@@ -1,5 +1,7 @@
 use std::collections::HashSet;
 
+use crate::ui_components::processor::{CharCounts, ThreadCounts};
+
 #[derive(Default, Clone)]
 pub struct CounterCounts {
     whitelisted_user_ids: HashSet<i64>,
@@ -7,6 +9,16 @@ pub struct CounterCounts {
     pub whitelisted_message: i32,
     pub total_user: i32,
     pub deleted_message: i32,
+    pub total_word: u64,
+    /// Char count of all messages in every mode
+    pub char_counts: CharCounts,
+    /// Whether this chat was counted without per-user stats
+    pub count_only: bool,
+    /// The highest message ID that was counted in this chat
+    pub highest_message_id: Option<i32>,
+    /// Whether the messages of this chat are grouped by thread
+    pub count_threads: bool,
+    pub threads: ThreadCounts,
 }
 
 impl CounterCounts {
@@ -25,12 +37,48 @@ impl CounterCounts {
         self.whitelisted_message += 1;
     }
 
+    /// Add the word and char count of a message
+    pub fn add_text_counts(&mut self, text: &str) {
+        self.total_word += text.split_whitespace().count() as u64;
+        self.char_counts.add(CharCounts::from_text(text));
+    }
+
+    pub fn update_highest_message(&mut self, message_id: i32) {
+        if self.highest_message_id.is_none_or(|id| id < message_id) {
+            self.highest_message_id = Some(message_id);
+        }
+    }
+
     pub fn add_deleted_message(&mut self, to_add: i32) {
         if to_add > 0 {
             self.deleted_message += to_add;
         }
     }
 
+    /// Percentage of deleted messages among all the checked and deleted messages
+    pub fn deleted_percentage(&self) -> f32 {
+        let total = self.total_message + self.deleted_message;
+        if total == 0 {
+            0.0
+        } else {
+            self.deleted_message as f32 / total as f32 * 100.0
+        }
+    }
+
+    /// Add the counts of another chat to this one
+    pub fn merge_from(&mut self, other: &CounterCounts) {
+        self.whitelisted_user_ids
+            .extend(other.whitelisted_user_ids.iter().copied());
+        self.total_message += other.total_message;
+        self.whitelisted_message += other.whitelisted_message;
+        self.deleted_message += other.deleted_message;
+        self.total_word += other.total_word;
+        self.char_counts.add(other.char_counts);
+        self.count_only = self.count_only && other.count_only;
+        self.count_threads = self.count_threads || other.count_threads;
+        self.threads.merge_from(&other.threads);
+    }
+
     pub fn total_whitelisted(&self) -> usize {
         self.whitelisted_user_ids.len()
     }
@@ -60,19 +60,29 @@ impl DatePickerHandler {
     /// Compare the given date with the current Start and End date
     /// to find the oldest and the newest date
     pub fn update_dates(&mut self, date: NaiveDate) {
-        if self.start.map_or(true, |current| current > date) {
+        if self.start.is_none_or(|current| current > date) {
             self.from = date;
             self.start = Some(date);
             self.last_from = Some(date);
         }
 
-        if self.end.map_or(true, |current_date| current_date < date) {
+        if self.end.is_none_or(|current_date| current_date < date) {
             self.to = date;
             self.end = Some(date);
             self.last_to = Some(date);
         }
     }
 
+    /// Expand the oldest and the newest date to include the dates of another handler
+    pub fn merge_dates(&mut self, other: &DatePickerHandler) {
+        if let Some(start) = other.start {
+            self.update_dates(start);
+        }
+        if let Some(end) = other.end {
+            self.update_dates(end);
+        }
+    }
+
     /// Whether the given date is whtin the current From and To range
     pub fn within_range(&self, date: NaiveDate) -> bool {
         date >= self.from && date <= self.to
@@ -1,4 +1,4 @@
-use chrono::{Days, Months};
+use chrono::{Days, Months, NaiveDate};
 
 use crate::ui_components::processor::{DatePickerHandler, NavigationType};
 
@@ -49,6 +49,43 @@ impl DateNavigator {
         }
     }
 
+    /// The date in the middle of the current From and To dates
+    pub fn center_date(&self) -> NaiveDate {
+        let from_date = self.handler.from;
+        let half_days = (self.handler.to - from_date).num_days().max(0) / 2;
+        from_date
+            .checked_add_days(Days::new(half_days as u64))
+            .unwrap()
+    }
+
+    /// Sets the From and To dates to 1 navigation step centered on the given date
+    pub fn jump_to(&mut self, date: NaiveDate) {
+        let (from_date, to_date) = match self.nav_type {
+            NavigationType::Day => (date, date),
+            NavigationType::Week => (
+                date.checked_sub_days(Days::new(3)).unwrap(),
+                date.checked_add_days(Days::new(3)).unwrap(),
+            ),
+            NavigationType::Month => {
+                let from_date = date.checked_sub_days(Days::new(15)).unwrap();
+                (
+                    from_date,
+                    from_date.checked_add_months(Months::new(1)).unwrap(),
+                )
+            }
+            NavigationType::Year => {
+                let from_date = date.checked_sub_days(Days::new(182)).unwrap();
+                (
+                    from_date,
+                    from_date.checked_add_months(Months::new(12)).unwrap(),
+                )
+            }
+        };
+
+        *self.handler().from() = from_date;
+        *self.handler().to() = to_date;
+    }
+
     fn next_day(&mut self) {
         let from_date = self.handler().from;
         let mut to_date = self.handler().to;
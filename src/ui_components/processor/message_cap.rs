@@ -0,0 +1,89 @@
+use std::sync::atomic::{AtomicBool, AtomicU32, Ordering};
+use std::sync::Arc;
+
+/// Limits the total number of messages counted in a chat. Shared by all the sessions that are
+/// counting the same chat so the limit applies to the total
+#[derive(Clone)]
+pub struct MessageCap {
+    limit: u32,
+    counted: Arc<AtomicU32>,
+    /// Whether a message past the limit was seen
+    exceeded: Arc<AtomicBool>,
+}
+
+impl MessageCap {
+    /// Tries to parse the limit from the given text. Returns None if the text is empty or not
+    /// a positive number
+    pub fn new(limit: &str) -> Option<Self> {
+        let limit = limit.trim().parse().ok().filter(|limit| *limit > 0)?;
+
+        Some(Self {
+            limit,
+            counted: Arc::new(AtomicU32::new(0)),
+            exceeded: Arc::new(AtomicBool::new(false)),
+        })
+    }
+
+    /// Whether the text is empty or a valid limit
+    pub fn is_valid(limit: &str) -> bool {
+        let limit = limit.trim();
+        limit.is_empty() || Self::new(limit).is_some()
+    }
+
+    /// Reserves one message from the limit. Returns false if the limit was already reached
+    pub fn take(&self) -> bool {
+        let taken = self
+            .counted
+            .fetch_update(Ordering::AcqRel, Ordering::Acquire, |counted| {
+                (counted < self.limit).then_some(counted + 1)
+            })
+            .is_ok();
+
+        if !taken {
+            self.exceeded.store(true, Ordering::Release);
+        }
+        taken
+    }
+
+    /// Whether a message was left uncounted because of the limit. A range with exactly as many
+    /// messages as the limit does not count as exceeded
+    pub fn exceeded(&self) -> bool {
+        self.exceeded.load(Ordering::Acquire)
+    }
+
+    pub fn limit(&self) -> u32 {
+        self.limit
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_limit() {
+        assert!(MessageCap::new("").is_none());
+        assert!(MessageCap::new("0").is_none());
+        assert!(MessageCap::new("-5").is_none());
+        assert!(MessageCap::new("abc").is_none());
+        assert_eq!(MessageCap::new(" 5 ").map(|cap| cap.limit()), Some(5));
+
+        assert!(MessageCap::is_valid(""));
+        assert!(MessageCap::is_valid("10"));
+        assert!(!MessageCap::is_valid("ten"));
+    }
+
+    #[test]
+    fn exceeded_only_past_the_limit() {
+        let cap = MessageCap::new("2").unwrap();
+        // Clones are shared between the sessions counting the same chat
+        let other_session = cap.clone();
+
+        assert!(cap.take());
+        assert!(other_session.take());
+        assert!(!cap.exceeded());
+
+        assert!(!other_session.take());
+        assert!(cap.exceeded());
+    }
+}
@@ -0,0 +1,83 @@
+use grammers_client::types::Message;
+use log::warn;
+use regex::{Regex, RegexBuilder};
+
+/// Decides whether a message should be counted based on its text
+#[derive(Clone)]
+pub enum MessageFilter {
+    Regex(Regex),
+    /// Lowercase text used for plain substring matching
+    Text(String),
+}
+
+impl MessageFilter {
+    /// Tries to create a regex filter, falls back to plain substring matching if the regex is
+    /// invalid. Returns None if the given filter is empty
+    pub fn new(filter: &str) -> Option<Self> {
+        let filter = filter.trim();
+        if filter.is_empty() {
+            return None;
+        }
+
+        match RegexBuilder::new(filter).case_insensitive(true).build() {
+            Ok(regex) => Some(MessageFilter::Regex(regex)),
+            Err(e) => {
+                warn!("Invalid regex given as the message filter, using substring matching. Error: {e}");
+                Some(MessageFilter::Text(filter.to_lowercase()))
+            }
+        }
+    }
+
+    /// Whether the filter could be compiled as a valid regex
+    pub fn is_valid_regex(filter: &str) -> bool {
+        let filter = filter.trim();
+        filter.is_empty() || Regex::new(filter).is_ok()
+    }
+
+    /// Whether the message text matches the filter
+    pub fn matches(&self, text: &str) -> bool {
+        match self {
+            MessageFilter::Regex(regex) => regex.is_match(text),
+            MessageFilter::Text(filter) => text.to_lowercase().contains(filter),
+        }
+    }
+}
+
+/// Decides whether a message should be counted based on its sender
+#[derive(Clone)]
+pub enum SenderFilter {
+    Id(i64),
+    /// Lowercase username without the leading @
+    Username(String),
+}
+
+impl SenderFilter {
+    /// Creates a filter from a user ID or a username. Returns None if the given filter is empty
+    pub fn new(filter: &str) -> Option<Self> {
+        let filter = filter.trim();
+        if filter.is_empty() {
+            return None;
+        }
+
+        if let Ok(id) = filter.parse() {
+            Some(SenderFilter::Id(id))
+        } else {
+            let username = filter.trim_start_matches('@').to_lowercase();
+            Some(SenderFilter::Username(username))
+        }
+    }
+
+    /// Whether the message was sent by the user of the filter
+    pub fn matches(&self, message: &Message) -> bool {
+        let Some(sender) = message.sender() else {
+            return false;
+        };
+
+        match self {
+            SenderFilter::Id(id) => sender.id() == *id,
+            SenderFilter::Username(username) => sender
+                .username()
+                .is_some_and(|name| name.to_lowercase() == *username),
+        }
+    }
+}
@@ -1,16 +1,28 @@
+mod app_settings;
+mod char_count;
 mod counter_counts;
 mod date_handler;
 mod date_navigator;
 mod font_dl;
+mod message_cap;
+mod message_filter;
 mod parsed_chat;
 mod states;
 mod tg_comms;
+mod thread_counts;
+mod undo_action;
 mod version_checker;
 
+pub use app_settings::*;
+pub use char_count::*;
 pub use counter_counts::*;
 pub use date_handler::*;
 pub use date_navigator::*;
 pub use font_dl::*;
+pub use message_cap::*;
+pub use message_filter::*;
 pub use parsed_chat::*;
 pub use states::*;
+pub use thread_counts::*;
+pub use undo_action::*;
 pub use version_checker::*;
@@ -1,7 +1,9 @@
+use chrono::NaiveDateTime;
 use eframe::egui::{vec2, Vec2};
 use grammers_client::types::Chat;
 use serde::{Deserialize, Serialize};
 use std::fmt::{self, Display};
+use std::time::Instant;
 use strum_macros::{Display as sDisplay, EnumIter};
 
 #[derive(Default)]
@@ -12,8 +14,9 @@ pub enum AppState {
     InitializedUI,
 }
 
-#[derive(PartialEq, EnumIter, sDisplay)]
+#[derive(Default, Clone, Copy, PartialEq, EnumIter, sDisplay, Serialize, Deserialize)]
 pub enum TabState {
+    #[default]
     Counter,
     #[strum(to_string = "User Table")]
     UserTable,
@@ -30,7 +33,7 @@ impl TabState {
 
     pub fn window_size(&self) -> Vec2 {
         match self {
-            TabState::Counter => vec2(550.0, 400.0),
+            TabState::Counter => vec2(750.0, 515.0),
             TabState::UserTable => vec2(1250.0, 700.0),
             TabState::Charts => vec2(1000.0, 700.0),
             TabState::Whitelist => vec2(550.0, 600.0),
@@ -45,11 +48,14 @@ pub enum ProcessState {
     InitialClientConnectionSuccessful(String),
     Counting(u8),
     InvalidStartChat,
+    InvalidMessageFilter,
     DataCopied,
     AuthorizationError,
     FileCreationFailed,
     UnauthorizedClient(String),
     NonExistingChat(String),
+    /// The chat exists but the session is not allowed to read it
+    PrivateChat(String),
     SendingTGCode,
     TGCodeSent,
     LogInWithCode,
@@ -59,15 +65,21 @@ pub enum ProcessState {
     NotSignedUp,
     UnknownError,
     LoggedIn(String),
+    ReconnectingSession(String),
+    SessionReconnected(String),
+    SessionReconnectFailed(String),
     EmptySelectedSession,
     InvalidPhoneOrAPI,
     InvalidAPIKeys,
     PasswordRequired,
-    FloodWait,
+    /// The time counting is expected to resume if known
+    FloodWait(Option<Instant>),
     UsersWhitelisted(usize),
     UsersBlacklisted(usize),
     LoadedWhitelistedUsers(usize, i32),
     LoadedBlacklistedUsers(usize, i32),
+    LoadingWhitelistedUsers(usize, usize),
+    LoadingBlacklistedUsers(usize, usize),
     FailedLoadWhitelistedUsers,
     FailedLoadBlacklistedUsers,
     WhitelistedUserRemoved(usize),
@@ -77,7 +89,30 @@ pub enum ProcessState {
     AddedToWhitelist,
     AddedToBlacklist,
     LatestMessageLoadingFailed,
-    DataExported(String),
+    DataExported(String, usize),
+    /// The message cap and the number of chats where counting stopped at the cap
+    MessageCapReached(u32, usize),
+    ChatsMerged(String),
+    PreviewingRange,
+    /// Total messages in the range, resolved chats and chats that failed to resolve
+    RangePreviewed(i64, usize, usize),
+    MergedChatExists(String),
+    /// The name of the imported chat and the number of imported rows
+    TableImported(String, usize),
+    TableImportFailed(String),
+    FetchingMembers(String),
+    /// The chat name and the number of users that are still members
+    MembersFiltered(String, usize),
+    /// The number of members that could be fetched and the total members of the chat
+    MembersIncomplete(usize, usize),
+    MembersUnavailable(String),
+    FetchingAdmins(String),
+    /// The chat name and the number of admins found
+    AdminsFetched(String, usize),
+    AdminsUnavailable(String),
+    AllDataReset,
+    ActionUndone,
+    FontsMissing,
 }
 
 impl ProcessState {
@@ -90,6 +125,21 @@ impl ProcessState {
             _ => ProcessState::Counting(0),
         }
     }
+
+    /// Text to keep in the status history. Progress updates of the same state share one text
+    pub fn history_text(&self) -> String {
+        match self {
+            ProcessState::Counting(_) => String::from("Checking messages"),
+            ProcessState::FloodWait(_) => String::from("Flood wait triggered"),
+            ProcessState::LoadingWhitelistedUsers(_, total) => {
+                format!("Loading {total} whitelisted users")
+            }
+            ProcessState::LoadingBlacklistedUsers(_, total) => {
+                format!("Loading {total} blacklisted users")
+            }
+            _ => self.to_string().trim_start_matches("Status: ").to_string(),
+        }
+    }
 }
 
 impl Display for ProcessState {
@@ -107,6 +157,7 @@ impl Display for ProcessState {
                 Ok(())
             }
             ProcessState::InvalidStartChat => write!(f, "Status: Could not detect any valid chat details"),
+            ProcessState::InvalidMessageFilter => write!(f, "Status: The message filter is not a valid regex, matching it as plain text"),
             ProcessState::DataCopied => {
                 write!(f, "Status: Selected table data copied.",)
             }
@@ -124,11 +175,23 @@ impl Display for ProcessState {
             ProcessState::NonExistingChat(name) => {
                 write!(f, "Status: The target chat {name} does not exist")
             }
+            ProcessState::PrivateChat(name) => {
+                write!(f, "Status: {name} is private or the session is not a member. Join the chat first or use a different session")
+            }
             ProcessState::SendingTGCode => write!(f, "Status: Trying to send Telegram login code"),
             ProcessState::TGCodeSent => write!(f, "Status: Telegram code was sent"),
             ProcessState::LogInWithCode => write!(f, "Status: Trying to login to the session with the code"),
             ProcessState::LogInWithPassword => write!(f, "Trying to login to the session with the password"),
             ProcessState::LoggedIn(name) => write!(f, "Status: Logged in session {name}"),
+            ProcessState::ReconnectingSession(name) => {
+                write!(f, "Status: Reconnecting to the session {name}")
+            }
+            ProcessState::SessionReconnected(name) => {
+                write!(f, "Status: Reconnected to the session {name}")
+            }
+            ProcessState::SessionReconnectFailed(name) => {
+                write!(f, "Status: Failed to reconnect to the session {name}. Check your connection and try again")
+            }
             ProcessState::InvalidTGCode => write!(f, "Status: Invalid TG Code given"),
             ProcessState::InvalidPassword => write!(f, "Status: Invalid password given"),
             ProcessState::NotSignedUp => write!(f, "Status: Account not signed up with this phone number"),
@@ -137,11 +200,20 @@ impl Display for ProcessState {
             ProcessState::InvalidPhoneOrAPI => write!(f, "Status: Unknown error acquired. Possibly invalid phone number given or API keys are invalid"),
             ProcessState::InvalidAPIKeys => write!(f, "Status: Failed to parse saved API keys. Are the API keys valid?"),
             ProcessState::PasswordRequired => write!(f, "Status: Account requires a password authentication"),
-            ProcessState::FloodWait => write!(f, "Status: Flood wait triggered. Will resume again soon"),
+            ProcessState::FloodWait(resume_at) => {
+                if let Some(resume_at) = resume_at {
+                    let remaining = resume_at.saturating_duration_since(Instant::now()).as_secs();
+                    write!(f, "Status: Flood wait triggered. Resuming in {remaining} seconds")
+                } else {
+                    write!(f, "Status: Flood wait triggered. Will resume again soon")
+                }
+            }
             ProcessState::UsersWhitelisted(num) => write!(f, "Status: Whitelisted {num} users"),
             ProcessState::UsersBlacklisted(num) => write!(f, "Status: Blacklisted {num} users"),
             ProcessState::LoadedWhitelistedUsers(success, failed) => write!(f, "Status: Loaded {success} whitelisted users. Failed to load {failed} users"),
             ProcessState::LoadedBlacklistedUsers(success, failed) => write!(f, "Status: Loaded {success} blacklisted users. Failed to load {failed} users"),
+            ProcessState::LoadingWhitelistedUsers(done, total) => write!(f, "Status: Loaded {done}/{total} whitelisted users"),
+            ProcessState::LoadingBlacklistedUsers(done, total) => write!(f, "Status: Loaded {done}/{total} blacklisted users"),
             ProcessState::FailedLoadWhitelistedUsers => write!(f, "Status: Failed to load whitelisted users due to invalid saved data. Old data has been removed"),
             ProcessState::FailedLoadBlacklistedUsers => write!(f, "Status: Failed to load blacklisted users due to invalid saved data. Old data has been removed"),
             ProcessState::WhitelistedUserRemoved(num) => write!(f, "Status: {num} whitelisted users removed"),
@@ -151,7 +223,63 @@ impl Display for ProcessState {
             ProcessState::AddedToWhitelist => write!(f, "Status: User added to whitelist"),
             ProcessState::AddedToBlacklist => write!(f, "Status: User added to blacklist"),
             ProcessState::LatestMessageLoadingFailed => write!(f, "Status: Failed to get the latest message"),
-            ProcessState::DataExported(location) => write!(f, "Status: Data exported to {location}"),
+            ProcessState::MessageCapReached(limit, total) => {
+                if *total > 1 {
+                    write!(f, "Status: Counting stopped in {total} chats after reaching the cap of {limit} messages")
+                } else {
+                    write!(f, "Status: Counting stopped after reaching the cap of {limit} messages")
+                }
+            }
+            ProcessState::DataExported(location, total) => {
+                if *total > 1 {
+                    write!(f, "Status: {total} files exported to {location}")
+                } else {
+                    write!(f, "Status: Data exported to {location}")
+                }
+            }
+            ProcessState::ChatsMerged(name) => write!(f, "Status: Merged chats into {name}"),
+            ProcessState::PreviewingRange => write!(f, "Status: Resolving the message range"),
+            ProcessState::RangePreviewed(total, chats, failed) => {
+                write!(f, "Status: This will count ~{total} messages across {chats} chats")?;
+                if *failed > 0 {
+                    write!(f, ". {failed} chats could not be resolved")?;
+                }
+                Ok(())
+            }
+            ProcessState::MergedChatExists(name) => write!(f, "Status: {name} already exists"),
+            ProcessState::TableImported(name, total) => {
+                write!(f, "Status: Imported {total} users into {name}")
+            }
+            ProcessState::TableImportFailed(error) => {
+                write!(f, "Status: Failed to import the table. Error: {error}")
+            }
+            ProcessState::FetchingMembers(name) => {
+                write!(f, "Status: Fetching the current members of {name}")
+            }
+            ProcessState::MembersFiltered(name, total) => {
+                write!(f, "Status: Showing {total} users that are still members of {name}")
+            }
+            ProcessState::MembersIncomplete(fetched, total) => {
+                write!(f, "Status: Only {fetched} of {total} members could be fetched. The table was not filtered")
+            }
+            ProcessState::MembersUnavailable(name) => {
+                write!(f, "Status: The members of {name} are hidden from this session")
+            }
+            ProcessState::FetchingAdmins(name) => {
+                write!(f, "Status: Fetching the admins of {name}")
+            }
+            ProcessState::AdminsFetched(name, total) => {
+                write!(f, "Status: Found {total} admins in {name}")
+            }
+            ProcessState::AdminsUnavailable(name) => {
+                write!(f, "Status: The admins of {name} are hidden from this session")
+            }
+            ProcessState::AllDataReset => write!(f, "Status: All data has been reset"),
+            ProcessState::ActionUndone => write!(f, "Status: Last action has been undone"),
+            ProcessState::FontsMissing => write!(
+                f,
+                "Status: Font files were not found and downloading is disabled. Some characters may not be shown"
+            ),
         }
     }
 }
@@ -163,13 +291,23 @@ pub enum ColumnName {
     Username,
     UserID,
     TotalMessage,
+    MessageShare,
     TotalWord,
     TotalChar,
     AverageWord,
     AverageChar,
+    PhotoCount,
+    VideoCount,
+    FileCount,
+    StickerCount,
+    Edited,
+    Duplicate,
     FirstMessageSeen,
     LastMessageSeen,
+    Activity,
     Whitelisted,
+    Admin,
+    SeenBy,
 }
 
 impl fmt::Display for ColumnName {
@@ -179,13 +317,23 @@ impl fmt::Display for ColumnName {
             ColumnName::Username => "Username",
             ColumnName::UserID => "User ID",
             ColumnName::TotalMessage => "Total Message",
+            ColumnName::MessageShare => "% of Total",
             ColumnName::TotalWord => "Total Word",
             ColumnName::TotalChar => "Total Char",
             ColumnName::AverageWord => "Average Word",
             ColumnName::AverageChar => "Average Char",
+            ColumnName::PhotoCount => "Photos",
+            ColumnName::VideoCount => "Videos",
+            ColumnName::FileCount => "Files",
+            ColumnName::StickerCount => "Stickers",
+            ColumnName::Edited => "Edited",
+            ColumnName::Duplicate => "Duplicates",
             ColumnName::FirstMessageSeen => "First Message Seen",
             ColumnName::LastMessageSeen => "Last Message Seen",
+            ColumnName::Activity => "Activity",
             ColumnName::Whitelisted => "Whitelisted",
+            ColumnName::Admin => "Admin",
+            ColumnName::SeenBy => "Seen By",
         };
         write!(f, "{name}")
     }
@@ -196,8 +344,28 @@ pub enum ChartType {
     #[default]
     Message,
     ActiveUser,
+    CumulativeUser,
+    AvgWordsOverTime,
     MessageWeekDay,
     ActiveUserWeekDay,
+    MessageHourOfDay,
+    ActiveUserHourOfDay,
+    ChatComparison,
+    Concentration,
+    WordCloud,
+}
+
+impl ChartType {
+    /// Whether the chart sums the data across all dates by weekday or by hour of day
+    pub fn is_period(&self) -> bool {
+        matches!(
+            self,
+            ChartType::MessageWeekDay
+                | ChartType::ActiveUserWeekDay
+                | ChartType::MessageHourOfDay
+                | ChartType::ActiveUserHourOfDay
+        )
+    }
 }
 
 impl Display for ChartType {
@@ -205,8 +373,15 @@ impl Display for ChartType {
         match self {
             ChartType::Message => write!(f, "Message"),
             ChartType::ActiveUser => write!(f, "Active User"),
+            ChartType::CumulativeUser => write!(f, "Cumulative User"),
+            ChartType::AvgWordsOverTime => write!(f, "Average Words"),
             ChartType::MessageWeekDay => write!(f, "Message Weekday"),
             ChartType::ActiveUserWeekDay => write!(f, "Active User Weekday"),
+            ChartType::MessageHourOfDay => write!(f, "Message Hour of Day"),
+            ChartType::ActiveUserHourOfDay => write!(f, "Active User Hour of Day"),
+            ChartType::ChatComparison => write!(f, "Chat Comparison"),
+            ChartType::Concentration => write!(f, "Concentration"),
+            ChartType::WordCloud => write!(f, "Word Cloud"),
         }
     }
 }
@@ -218,6 +393,8 @@ pub enum ChartTiming {
     Daily,
     Weekly,
     Monthly,
+    /// Grouped by the custom interval of the chart
+    Custom,
 }
 
 impl Display for ChartTiming {
@@ -227,11 +404,23 @@ impl Display for ChartTiming {
             ChartTiming::Hourly => write!(f, "Hourly"),
             ChartTiming::Weekly => write!(f, "Weekly"),
             ChartTiming::Monthly => write!(f, "Monthly"),
+            ChartTiming::Custom => write!(f, "Custom"),
         }
     }
 }
 
-#[derive(Deserialize, Serialize, PartialEq, Eq, Hash)]
+/// Summary of a single counted message that gets streamed to a JSONL file
+#[derive(Serialize)]
+pub struct StreamedMessage {
+    pub chat: String,
+    pub message_id: i32,
+    pub user_id: i64,
+    pub timestamp: NaiveDateTime,
+    pub total_word: u32,
+    pub total_char: u32,
+}
+
+#[derive(Clone, Deserialize, Serialize, PartialEq, Eq, Hash)]
 pub struct PackedWhitelistedUser {
     pub hex_value: String,
     pub seen_by: String,
@@ -254,7 +443,7 @@ impl UnpackedWhitelistedUser {
     }
 }
 
-#[derive(Deserialize, Serialize, PartialEq, Eq, Hash)]
+#[derive(Clone, Deserialize, Serialize, PartialEq, Eq, Hash)]
 pub struct PackedBlacklistedUser {
     pub hex_value: String,
     pub seen_by: String,
@@ -1,17 +1,37 @@
-use chrono::{Local, TimeZone};
+use chrono::{Local, NaiveDateTime, TimeZone};
+use grammers_client::types::{Chat, Message};
 use log::{error, info};
+use std::io::Write;
 use std::sync::atomic::Ordering;
+use std::time::{Duration, Instant};
 
 use crate::tg_handler::{ProcessError, ProcessResult, ProcessStart};
-use crate::ui_components::processor::ProcessState;
+use crate::ui_components::processor::{thread_id, CharCounts, ProcessState, StreamedMessage};
 use crate::ui_components::MainWindow;
-use crate::utils::to_chart_name;
+use crate::utils::{is_session_bound_chat, save_checkpoint, to_chart_name, write_streamed_message};
 
 impl MainWindow {
     /// Checks if there are any new message from the async side
     pub fn check_receiver(&mut self) -> bool {
         if let Ok(data) = self.tg_receiver.try_recv() {
             match data {
+                ProcessResult::InitialSessionSuccess((clients, success, _))
+                    if self.session.is_reconnecting() =>
+                {
+                    let name = self.session.take_reconnecting().unwrap();
+                    self.stop_process();
+
+                    for client in clients {
+                        self.tg_clients.insert(client.name(), client);
+                    }
+
+                    self.process_state = if success.contains(&name) {
+                        info!("Reconnected to the session {name}");
+                        ProcessState::SessionReconnected(name)
+                    } else {
+                        ProcessState::SessionReconnectFailed(name)
+                    };
+                }
                 ProcessResult::InitialSessionSuccess((clients, success, failed)) => {
                     let mut status_text = if success.is_empty() {
                         String::new()
@@ -25,11 +45,13 @@ impl MainWindow {
                     }
 
                     for client in clients {
+                        self.counter.enable_session(client.name());
                         self.tg_clients.insert(client.name(), client);
                     }
 
                     self.process_state =
                         ProcessState::InitialClientConnectionSuccessful(status_text);
+                    self.cancel_load.store(false, Ordering::Release);
                     self.load_whitelisted_users();
                     self.load_blacklisted_users();
                 }
@@ -58,9 +80,17 @@ impl MainWindow {
                     // Progress only if 1 session is remaining to be completed or it was 0 (0 in normal counting)
                     // Also recreate table data after counting session ends for a chat
                     if self.counter.session_remaining() <= 1 {
+                        if self.counter.cap_reached() {
+                            info!("Counting stopped as the message cap was reached");
+                            self.counter.add_capped_chat();
+                        }
+                        self.close_stream_writer();
+                        self.save_ongoing_checkpoint();
+                        self.t_chart().fill_missing_times();
                         self.t_table().create_rows();
                         let total_user = self.t_table().get_total_users_full();
                         self.t_count().set_total_user(total_user as i32);
+                        self.auto_save_ongoing();
                         self.go_next_or_stop();
                     } else {
                         self.counter.reduce_session();
@@ -68,6 +98,7 @@ impl MainWindow {
                 }
                 ProcessResult::CountingMessage(count_data) => {
                     self.process_state = self.process_state.next_dot();
+                    self.counter.add_session_message(&count_data.name());
 
                     let message = count_data.message();
                     let start_from = count_data.start_at();
@@ -83,37 +114,58 @@ impl MainWindow {
                     let sender = message.sender();
 
                     let user_id = if let Some(c) = &sender { c.id() } else { 0 };
+                    let is_bot = matches!(&sender, Some(Chat::User(user)) if user.is_bot());
 
                     let blacklisted = self.blacklist.is_user_blacklisted(user_id);
-
-                    let (user_id, full_name, user_name) = self.t_table().add_user(
-                        sender,
-                        local_time_date,
-                        local_time_datetime,
-                        count_data.name(),
-                        blacklisted,
-                    );
-
-                    let chart_user = to_chart_name(user_name, &full_name, user_id);
+                    let whitelisted = self.whitelist.is_user_whitelisted(user_id);
 
                     if !blacklisted {
-                        self.t_chart().add_user(chart_user.clone(), user_id);
-                        self.t_table().count_user_message(
-                            user_id,
-                            message,
+                        let chat_name =
+                            self.counter.selected_chat_name(self.counter.ongoing_chat());
+                        self.stream_message(&chat_name, user_id, message, local_time_datetime);
+                    }
+
+                    // Count only mode skips all per-user stats and only updates the counter totals
+                    if !self.t_count().count_only {
+                        let (user_id, full_name, user_name) = self.t_table().add_user(
+                            sender,
                             local_time_date,
                             local_time_datetime,
+                            count_data.name(),
+                            blacklisted,
                         );
-                    }
 
-                    let whitelisted = self.whitelist.is_user_whitelisted(user_id);
+                        let chart_user = to_chart_name(user_name, &full_name, user_id);
+
+                        if !blacklisted {
+                            self.t_chart().add_user(chart_user.clone(), user_id, is_bot);
+                            let char_config = self.settings.char_count;
+                            self.t_table().count_user_message(
+                                user_id,
+                                message,
+                                local_time_date,
+                                local_time_datetime,
+                                char_config,
+                            );
+                            let word_count = message.text().split_whitespace().count() as u64;
+                            self.t_chart().add_message(
+                                local_time_datetime,
+                                local_time_date,
+                                chart_user,
+                                word_count,
+                                &count_data.name(),
+                            );
+                            self.t_chart().add_words(message.text());
+                        }
 
-                    if user_id != 0 && whitelisted && !blacklisted {
-                        self.t_table().set_as_whitelisted(&[user_id]);
-                    }
+                        if user_id != 0 && whitelisted && !blacklisted {
+                            // Rows get recreated once the reload threshold is reached
+                            self.t_table().mark_as_whitelisted(&[user_id]);
+                        }
 
-                    let total_user = self.t_table().get_total_users_full();
-                    self.t_count().set_total_user(total_user as i32);
+                        let total_user = self.t_table().get_total_users_full();
+                        self.t_count().set_total_user(total_user as i32);
+                    }
 
                     let total_to_iter = start_from - end_at;
                     let message_value = 100.0 / total_to_iter as f32;
@@ -139,6 +191,13 @@ impl MainWindow {
                     };
 
                     self.t_count().add_one_total_message();
+                    self.t_count().add_text_counts(message.text());
+                    self.t_count().update_highest_message(start_from);
+                    if self.t_count().count_threads {
+                        self.t_count()
+                            .threads
+                            .add_message(thread_id(message), user_id);
+                    }
                     if whitelisted {
                         self.t_count().add_one_whitelisted_message();
                         self.t_count().add_whitelisted_user(user_id);
@@ -156,15 +215,7 @@ impl MainWindow {
                             .set_bar_percentage(processed_percentage / 100.0);
                     }
 
-                    if !blacklisted {
-                        self.t_chart().add_message(
-                            local_time_datetime,
-                            local_time_date,
-                            chart_user,
-                            &count_data.name(),
-                        );
-                    }
-                    if self.t_table().reload_count() > 100 {
+                    if self.t_table().reload_count() > self.counter.get_reload_threshold() {
                         self.t_table().create_rows();
                         self.t_table().reset_reload_count();
                     }
@@ -209,6 +260,18 @@ impl MainWindow {
                             error!("Failed to get the latest message ID");
                             self.process_state = ProcessState::LatestMessageLoadingFailed;
                         }
+                        ProcessError::PrivateChat(chat_name) => {
+                            error!("The session does not have access to {chat_name}");
+                            self.process_state = ProcessState::PrivateChat(chat_name);
+                        }
+                        ProcessError::MembersUnavailable(chat_name) => {
+                            error!("The session cannot list the members of {chat_name}");
+                            self.process_state = ProcessState::MembersUnavailable(chat_name);
+                        }
+                        ProcessError::AdminsUnavailable(chat_name) => {
+                            error!("The session cannot list the admins of {chat_name}");
+                            self.process_state = ProcessState::AdminsUnavailable(chat_name);
+                        }
                     }
                     self.go_next_or_stop();
                 }
@@ -230,15 +293,27 @@ impl MainWindow {
                     self.stop_process();
                     self.session.reset_data();
                     let incomplete_client = self.incomplete_tg_client.take().unwrap();
+                    self.counter.enable_session(incomplete_client.name());
                     self.tg_clients
                         .insert(incomplete_client.name(), incomplete_client);
                     self.process_state = ProcessState::LoggedIn(name);
                 }
-                ProcessResult::FloodWait => {
+                ProcessResult::FloodWait(seconds) => {
                     info!("Flood wait triggered");
-                    self.process_state = ProcessState::FloodWait;
+                    let resume_at = seconds
+                        .map(|seconds| Instant::now() + Duration::from_secs(u64::from(seconds)));
+                    self.process_state = ProcessState::FloodWait(resume_at);
+                }
+                ProcessResult::WhitelistLoadProgress(count) => {
+                    let (done, total) = self.whitelist.add_load_progress(count);
+                    self.process_state = ProcessState::LoadingWhitelistedUsers(done, total);
                 }
-                ProcessResult::UnpackedWhitelist(chats, failed_chats) => {
+                ProcessResult::BlacklistLoadProgress(count) => {
+                    let (done, total) = self.blacklist.add_load_progress(count);
+                    self.process_state = ProcessState::LoadingBlacklistedUsers(done, total);
+                }
+                ProcessResult::UnpackedWhitelist(chats, failed_chats, unloaded) => {
+                    self.whitelist.keep_unloaded(unloaded);
                     for chat in chats {
                         let username = if let Some(name) = chat.user_chat.username() {
                             name.to_string()
@@ -262,7 +337,8 @@ impl MainWindow {
                     self.process_state =
                         ProcessState::LoadedWhitelistedUsers(total_chat, failed_chat_num);
                 }
-                ProcessResult::UnpackedBlacklist(chats, failed_chats) => {
+                ProcessResult::UnpackedBlacklist(chats, failed_chats, unloaded) => {
+                    self.blacklist.keep_unloaded(unloaded);
                     let mut names = Vec::new();
                     let mut user_ids = Vec::new();
 
@@ -342,7 +418,7 @@ impl MainWindow {
                     let chart_name = to_chart_name(username.clone(), &full_name, user_id);
 
                     for chart in self.chart_all() {
-                        chart.clear_blacklisted(&[chart_name.clone()]);
+                        chart.clear_blacklisted(std::slice::from_ref(&chart_name));
                     }
 
                     for table in self.table_all() {
@@ -360,10 +436,21 @@ impl MainWindow {
                     self.blacklist.save_blacklisted_users(false);
                     self.process_state = ProcessState::AddedToBlacklist;
                 }
+                ProcessResult::ChatExists(_, start_at, end_at) if self.counter.previewing() => {
+                    // Because we count both the start and ending message ID
+                    self.preview_resolved(Some(start_at - end_at + 1));
+                }
                 ProcessResult::ChatExists(chat_name, start_at, end_at) => {
                     // Because we count both the start and ending message ID
                     let total_to_count = start_at - end_at + 1;
-                    let total_session = self.tg_clients.len();
+                    let mut counting_sessions = self.counting_sessions();
+                    // Splitting these between sessions would count a different chat in each
+                    // session so only the session that checked the chat counts it
+                    if is_session_bound_chat(&chat_name) {
+                        info!("{chat_name} is bound to a session, counting with a single session");
+                        counting_sessions.truncate(1);
+                    }
+                    let total_session = counting_sessions.len();
                     let per_session_value = total_to_count / total_session as i32;
 
                     info!("Each session to process {}~ messages", per_session_value);
@@ -374,12 +461,19 @@ impl MainWindow {
                     let mut ongoing_end_at = start_at - per_session_value;
 
                     let mut negative_added = false;
+                    let filter = self.counter.message_filter();
+                    let sender_filter = self.counter.sender_filter();
+                    let cap = self.counter.active_cap();
+                    let skip_service = self.counter.skip_service();
+                    let skip_stickers = self.counter.skip_stickers();
                     self.cancel_count.store(false, Ordering::Relaxed);
-                    for (index, client) in self.tg_clients.values().enumerate() {
+                    for (index, client) in counting_sessions.into_iter().enumerate() {
                         let cancel = self.cancel_count.clone();
+                        let filter = filter.clone();
+                        let sender_filter = sender_filter.clone();
+                        let cap = cap.clone();
                         self.counter.add_session(client.name());
 
-                        let client = client.clone();
                         let chat_name = chat_name.clone();
                         if index == total_session - 1 {
                             ongoing_end_at = end_at;
@@ -398,6 +492,11 @@ impl MainWindow {
                                     Some(ongoing_end_at),
                                     true,
                                     cancel,
+                                    filter,
+                                    sender_filter,
+                                    cap,
+                                    skip_service,
+                                    skip_stickers,
                                 ))
                                 .await;
                         });
@@ -418,6 +517,33 @@ impl MainWindow {
                         }
                     }
                 }
+                ProcessResult::ChatMembers(chat_name, members, total) => {
+                    self.stop_process();
+
+                    // Telegram limits how many members can be listed in large chats. Filtering
+                    // with a partial list would hide users that are still members
+                    if members.len() < total {
+                        info!("Only {} of {total} members were fetched", members.len());
+                        self.process_state = ProcessState::MembersIncomplete(members.len(), total);
+                    } else if self.counter.contains_chat(&chat_name) {
+                        let index = self.counter.chat_index(&chat_name);
+                        let table = self.table_all().nth(index).unwrap();
+                        table.set_members(Some(members));
+                        let remaining = table.get_total_user();
+                        self.process_state = ProcessState::MembersFiltered(chat_name, remaining);
+                    }
+                }
+                ProcessResult::ChatAdmins(chat_name, admins) => {
+                    self.stop_process();
+
+                    if self.counter.contains_chat(&chat_name) {
+                        let index = self.counter.chat_index(&chat_name);
+                        let total = admins.len();
+                        self.chart_all().nth(index).unwrap().set_admins(&admins);
+                        self.table_all().nth(index).unwrap().set_admins(admins);
+                        self.process_state = ProcessState::AdminsFetched(chat_name, total);
+                    }
+                }
             }
             true
         } else {
@@ -427,11 +553,88 @@ impl MainWindow {
 
     pub fn stop_process(&mut self) {
         self.is_processing = false;
+        self.counter.end_preview();
         self.counter.counting_ended();
+        self.close_stream_writer();
+    }
+
+    /// Saves the highest counted message ID of the ongoing chat unless the count was cancelled or
+    /// left messages uncounted
+    fn save_ongoing_checkpoint(&mut self) {
+        if self.cancel_count.load(Ordering::Acquire) {
+            return;
+        }
+
+        let chat_name = self.counter.selected_chat_name(self.counter.ongoing_chat());
+        if !self.counter.covered_full_range(&chat_name) {
+            info!("{chat_name} was not fully counted, not saving a checkpoint");
+            return;
+        }
+
+        if let Some(message_id) = self.t_count().highest_message_id {
+            save_checkpoint(&chat_name, message_id);
+        }
+    }
+
+    /// Exports the table of the ongoing chat if auto-save is enabled and the count was not cancelled
+    fn auto_save_ongoing(&mut self) {
+        if !self.settings.auto_save || self.cancel_count.load(Ordering::Acquire) {
+            return;
+        }
+
+        if self.t_table().get_total_users_full() == 0 {
+            return;
+        }
+
+        let chat_name = self.counter.selected_chat_name(self.counter.ongoing_chat());
+        let delimiter = self.settings.csv_delimiter;
+        let anonymize = self.settings.anonymize_export;
+        info!("Auto-saving the table of {chat_name}");
+        self.t_table().export_data(&chat_name, delimiter, anonymize);
+        self.counter.add_auto_saved();
+    }
+
+    /// Appends the message summary to the stream file if streaming is enabled
+    fn stream_message(
+        &mut self,
+        chat_name: &str,
+        user_id: i64,
+        message: &Message,
+        datetime: NaiveDateTime,
+    ) {
+        let Some(writer) = self.stream_writer.as_mut() else {
+            return;
+        };
+
+        let message_text = message.text();
+        let streamed = StreamedMessage {
+            chat: chat_name.to_string(),
+            message_id: message.id(),
+            user_id,
+            timestamp: datetime,
+            total_word: message_text.split_whitespace().count() as u32,
+            total_char: CharCounts::from_text(message_text).get(self.settings.char_count),
+        };
+
+        if let Err(e) = write_streamed_message(writer, &streamed) {
+            error!("Failed to write to the message stream file. Stopping streaming. Error: {e}");
+            self.stream_writer = None;
+        }
+    }
+
+    /// Flushes and closes the stream file if one is open
+    fn close_stream_writer(&mut self) {
+        if let Some(mut writer) = self.stream_writer.take() {
+            if let Err(e) = writer.flush() {
+                error!("Failed to flush the message stream file. Error: {e}");
+            }
+        }
     }
 
     fn go_next_or_stop(&mut self) {
-        if self.counter.counting() {
+        if self.counter.previewing() {
+            self.preview_resolved(None);
+        } else if self.counter.counting() {
             self.counter.increment_ongoing();
             self.process_next_count();
         } else {
@@ -0,0 +1,64 @@
+use grammers_client::types::Message;
+use grammers_tl_types as tl;
+use std::collections::{HashMap, HashSet};
+
+/// Message stats of a single reply thread or forum topic
+#[derive(Default, Clone)]
+pub struct ThreadStats {
+    pub total_message: u32,
+    users: HashSet<i64>,
+}
+
+impl ThreadStats {
+    pub fn total_user(&self) -> usize {
+        self.users.len()
+    }
+}
+
+/// Message stats of every thread in a chat. Messages outside of any thread are kept under `None`
+#[derive(Default, Clone)]
+pub struct ThreadCounts {
+    threads: HashMap<Option<i32>, ThreadStats>,
+}
+
+impl ThreadCounts {
+    pub fn add_message(&mut self, thread_id: Option<i32>, user_id: i64) {
+        let thread = self.threads.entry(thread_id).or_default();
+        thread.total_message += 1;
+        thread.users.insert(user_id);
+    }
+
+    /// Add the thread stats of another chat to this one
+    pub fn merge_from(&mut self, other: &ThreadCounts) {
+        for (thread_id, stats) in &other.threads {
+            let thread = self.threads.entry(*thread_id).or_default();
+            thread.total_message += stats.total_message;
+            thread.users.extend(stats.users.iter().copied());
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.threads.is_empty()
+    }
+
+    /// All the threads sorted by the number of messages, the most active first
+    pub fn sorted_threads(&self) -> Vec<(Option<i32>, &ThreadStats)> {
+        let mut threads: Vec<(Option<i32>, &ThreadStats)> = self
+            .threads
+            .iter()
+            .map(|(thread_id, stats)| (*thread_id, stats))
+            .collect();
+        threads.sort_by_key(|(_, stats)| std::cmp::Reverse(stats.total_message));
+        threads
+    }
+}
+
+/// The ID of the first message of the thread or forum topic the message belongs to
+pub fn thread_id(message: &Message) -> Option<i32> {
+    match message.reply_header() {
+        Some(tl::enums::MessageReplyHeader::Header(header)) => {
+            header.reply_to_top_id.or(header.reply_to_msg_id)
+        }
+        _ => None,
+    }
+}
@@ -0,0 +1,46 @@
+use chrono::NaiveDate;
+use grammers_client::types::Chat;
+
+use crate::ui_components::tab_ui::{RemovedChartUser, UserRowData};
+
+/// A whitelisted or blacklisted user with all the details needed to add them back
+#[derive(Clone)]
+pub struct ListedUser {
+    pub name: String,
+    pub username: String,
+    pub id: i64,
+    pub belongs_to: Chat,
+    pub seen_by: String,
+}
+
+/// The last whitelist or blacklist change, kept so it can be reverted
+pub enum UndoAction {
+    /// Users that were whitelisted from the User Table
+    Whitelisted(Vec<i64>),
+    /// Users that were blacklisted from the User Table with the data removed from each chat
+    Blacklisted {
+        ids: Vec<i64>,
+        table_rows: Vec<Vec<(NaiveDate, UserRowData)>>,
+        chart_users: Vec<Vec<RemovedChartUser>>,
+    },
+    /// Users that were deleted from the whitelist
+    WhitelistRemoved(Vec<ListedUser>),
+    /// Users that were deleted from the blacklist
+    BlacklistRemoved(Vec<ListedUser>),
+}
+
+impl UndoAction {
+    /// Short description of the action for the undo button
+    pub fn description(&self) -> String {
+        match self {
+            UndoAction::Whitelisted(ids) => format!("whitelisting {} users", ids.len()),
+            UndoAction::Blacklisted { ids, .. } => format!("blacklisting {} users", ids.len()),
+            UndoAction::WhitelistRemoved(users) => {
+                format!("removing {} users from whitelist", users.len())
+            }
+            UndoAction::BlacklistRemoved(users) => {
+                format!("removing {} users from blacklist", users.len())
+            }
+        }
+    }
+}
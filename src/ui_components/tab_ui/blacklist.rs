@@ -7,12 +7,17 @@ use egui_selectable_table::{
 };
 use grammers_client::types::Chat;
 use log::{error, info};
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 
 use crate::tg_handler::ProcessStart;
-use crate::ui_components::processor::{ColumnName, PackedBlacklistedUser, ProcessState};
+use crate::ui_components::processor::{
+    ColumnName, ListedUser, PackedBlacklistedUser, ProcessState, UndoAction,
+};
 use crate::ui_components::MainWindow;
-use crate::utils::{get_blacklisted, save_blacklisted_users, separate_blacklist_by_seen};
+use crate::utils::{
+    backup_blacklisted, display_name, get_blacklisted, save_blacklisted_users,
+    separate_blacklist_by_seen,
+};
 
 #[derive(Default)]
 struct Config {
@@ -81,8 +86,12 @@ impl ColumnOperations<BlackListRowData, ColumnName, Config> for ColumnName {
             )
             .interact(Sense::drag());
 
-        if show_tooltip {
-            label = label.on_hover_text(row_text);
+        // The saved user can only be used by the session that observed it
+        let seen_by = format!("Seen by session: {}", row_data.seen_by);
+        label = if show_tooltip {
+            label.on_hover_text(format!("{row_text}\n{seen_by}"))
+        } else {
+            label.on_hover_text(seen_by)
         };
         label.context_menu(|ui| {
             if ui.button("Deleted Selected").clicked() {
@@ -118,6 +127,16 @@ impl BlackListRowData {
             seen_by,
         }
     }
+
+    fn to_listed_user(&self) -> ListedUser {
+        ListedUser {
+            name: self.name.clone(),
+            username: self.username.clone(),
+            id: self.id,
+            belongs_to: self.belongs_to.clone(),
+            seen_by: self.seen_by.clone(),
+        }
+    }
 }
 
 pub struct BlacklistData {
@@ -125,6 +144,14 @@ pub struct BlacklistData {
     target_username: String,
     failed_blacklist: i32,
     all_ids: HashSet<i64>,
+    /// Total saved users that are being loaded
+    load_total: usize,
+    /// Saved users processed so far during loading
+    load_done: usize,
+    /// Saved users that were not loaded, kept so saving does not remove them
+    unloaded: Vec<PackedBlacklistedUser>,
+    /// Saved users of other sessions that saw an already added user, keyed by the user id
+    other_sessions: HashMap<i64, Vec<PackedBlacklistedUser>>,
 }
 impl Default for BlacklistData {
     fn default() -> Self {
@@ -142,6 +169,10 @@ impl Default for BlacklistData {
             target_username: String::new(),
             failed_blacklist: 0,
             all_ids: HashSet::new(),
+            load_total: 0,
+            load_done: 0,
+            unloaded: Vec::new(),
+            other_sessions: HashMap::new(),
         }
     }
 }
@@ -156,14 +187,32 @@ impl BlacklistData {
         belongs_to: Chat,
         seen_by: String,
     ) {
-        let name = if name.is_empty() {
-            String::from("Deleted Account")
-        } else {
-            name
-        };
+        let name = display_name(&name);
+
+        // The same user can be seen by multiple sessions. Keep only the first row but save the
+        // entry of every session so none of them are lost
+        if !self.all_ids.insert(id) {
+            let row_seen_by = self
+                .table
+                .get_all_rows()
+                .values()
+                .find(|row| row.row_data.id == id)
+                .map(|row| row.row_data.seen_by.clone());
+            let packed = self.other_sessions.entry(id).or_default();
+
+            if row_seen_by.as_ref() != Some(&seen_by)
+                && !packed.iter().any(|user| user.seen_by == seen_by)
+            {
+                info!("{name} is already in the blacklist, keeping the entry seen by {seen_by}");
+                packed.push(PackedBlacklistedUser::new(
+                    belongs_to.pack().to_hex(),
+                    seen_by,
+                ));
+            }
+            return;
+        }
 
         info!("Adding {name} to blacklist, seen by {seen_by}");
-        self.all_ids.insert(id);
         self.table.add_modify_row(|_rows| {
             let to_add = BlackListRowData::new(name, username, id, belongs_to, seen_by);
             Some(to_add)
@@ -187,45 +236,79 @@ impl BlacklistData {
             ));
         });
 
+        packed_chats.extend(self.unloaded.iter().cloned());
+        packed_chats.extend(self.other_sessions.values().flatten().cloned());
         save_blacklisted_users(packed_chats, overwrite);
     }
 
+    /// Keeps saved users that could not be loaded so they stay in the saved list
+    pub fn keep_unloaded(&mut self, users: Vec<PackedBlacklistedUser>) {
+        self.unloaded.extend(users);
+    }
+
     /// Removes selected row from blacklist and saves the result
-    fn remove_selected(&mut self) -> Vec<i64> {
+    fn remove_selected(&mut self) -> Vec<ListedUser> {
         let active_rows = self.table.get_selected_rows();
 
-        let mut row_ids = Vec::new();
+        let mut removed_users = Vec::new();
         for i in &active_rows {
             info!(
                 "Removing user {} | {} from blacklist",
                 i.row_data.username, i.row_data.id
             );
             self.all_ids.remove(&i.row_data.id);
+            self.other_sessions.remove(&i.row_data.id);
             self.table.add_modify_row(|rows| {
                 rows.remove(&i.id);
-                row_ids.push(i.row_data.id);
+                removed_users.push(i.row_data.to_listed_user());
                 None
             });
         }
         self.save_blacklisted_users(true);
-        row_ids
+        removed_users
     }
 
     /// Removes all row from blacklist and saves the result
-    fn remove_all(&mut self) -> Vec<i64> {
+    fn remove_all(&mut self) -> Vec<ListedUser> {
         info!("Removing all users from blacklist");
-        let row_keys = self.all_ids.iter().copied().collect();
+        let removed_users = self
+            .table
+            .get_all_rows()
+            .values()
+            .map(|row| row.row_data.to_listed_user())
+            .collect();
         self.table.clear_all_rows();
+        self.unloaded.clear();
+        self.other_sessions.clear();
         self.save_blacklisted_users(true);
         self.all_ids.clear();
 
-        row_keys
+        removed_users
+    }
+
+    /// Removes the given users from blacklist and saves the result
+    pub fn remove_users(&mut self, user_ids: &[i64]) {
+        for id in user_ids {
+            self.all_ids.remove(id);
+            self.other_sessions.remove(id);
+        }
+        self.table.add_modify_row(|rows| {
+            rows.retain(|_, row| !user_ids.contains(&row.row_data.id));
+            None
+        });
+        self.save_blacklisted_users(true);
     }
 
     pub fn clear_text_box(&mut self) {
         self.target_username.clear();
     }
 
+    /// Add to the number of saved users processed and return the processed and total count
+    pub fn add_load_progress(&mut self, count: usize) -> (usize, usize) {
+        self.load_done += count;
+        (self.load_done, self.load_total)
+    }
+
     pub fn increase_failed_by(&mut self, count: i32) {
         self.failed_blacklist += count;
     }
@@ -244,8 +327,7 @@ impl MainWindow {
         if self.blacklist.table.config.deleted_selected {
             self.blacklist.table.config.deleted_selected = false;
             let deleted = self.blacklist.remove_selected();
-            let total_to_remove = deleted.len();
-            self.process_state = ProcessState::BlacklistedUserRemoved(total_to_remove);
+            self.blacklist_users_removed(deleted);
         };
 
         Grid::new("blacklist Grid")
@@ -298,15 +380,15 @@ then right click on User Table to blacklist",
                 .clicked()
             {
                 let deleted = self.blacklist.remove_selected();
-                let total_to_remove = deleted.len();
-                self.process_state = ProcessState::BlacklistedUserRemoved(total_to_remove);
+                self.blacklist_users_removed(deleted);
             };
             if ui
                 .button("Delete All")
                 .on_hover_text("Delete all blacklisted users")
                 .clicked()
             {
-                let _ = self.blacklist.remove_all();
+                let deleted = self.blacklist.remove_all();
+                self.blacklist_users_removed(deleted);
                 self.process_state = ProcessState::AllBlacklistRemoved;
             };
         });
@@ -325,26 +407,49 @@ then right click on User Table to blacklist",
         });
     }
 
+    /// Keeps the users removed from the blacklist for undo
+    fn blacklist_users_removed(&mut self, deleted: Vec<ListedUser>) {
+        self.process_state = ProcessState::BlacklistedUserRemoved(deleted.len());
+        if !deleted.is_empty() {
+            self.last_action = Some(UndoAction::BlacklistRemoved(deleted));
+        }
+    }
+
     pub fn load_blacklisted_users(&mut self) {
         // This function will never be called if there are no sessions detected.
         // Unnecessary to handle in case `self.tg_clients` is empty
 
         let all_blacklisted_users = get_blacklisted();
 
-        if all_blacklisted_users.is_err() {
+        let Ok((blacklisted_users, dropped)) = all_blacklisted_users else {
             // This case means it failed to deserialize the json or is using the old blacklist json format
-            // All previous data will be removed
-            error!("Failed to deserialize the blacklist users json file. Deleting saved json data");
-            save_blacklisted_users(Vec::new(), true);
+            // The previous data is moved to a backup file and the saved list starts empty. If the
+            // backup fails, the file is kept as it is
+            error!("Failed to deserialize the blacklist users json file");
+            if backup_blacklisted() {
+                info!("Deleting saved blacklist json data");
+                save_blacklisted_users(Vec::new(), true);
+            }
             self.process_state = ProcessState::FailedLoadBlacklistedUsers;
             self.is_processing = false;
             return;
-        }
+        };
+
+        // Malformed entries are dropped while reading, count them as failed to load
+        self.blacklist.increase_failed_by(dropped as i32);
 
         // separate blacklist data by seen_by as the key and hex as the value
-        let separated_data = separate_blacklist_by_seen(all_blacklisted_users.unwrap());
+        let separated_data = separate_blacklist_by_seen(blacklisted_users);
+
+        self.blacklist.load_done = 0;
+        self.blacklist.unloaded.clear();
+        self.blacklist.other_sessions.clear();
+        self.blacklist.load_total = separated_data.values().map(Vec::len).sum();
 
         if separated_data.is_empty() {
+            if dropped > 0 {
+                self.process_state = ProcessState::LoadedBlacklistedUsers(0, dropped as i32);
+            }
             self.is_processing = false;
             return;
         }
@@ -360,6 +465,13 @@ then right click on User Table to blacklist",
                     "{seen_by} client does not exist! Ignoring {total_blacklist} blacklisted users"
                 );
                 self.blacklist.increase_failed_by(total_blacklist as i32);
+                self.blacklist.keep_unloaded(
+                    hex_data
+                        .into_iter()
+                        .map(|hex| PackedBlacklistedUser::new(hex, seen_by.clone()))
+                        .collect(),
+                );
+                self.blacklist.add_load_progress(total_blacklist);
 
                 let success_blacklist = self.blacklist.row_len();
                 let failed_blacklist = self.blacklist.failed_blacklist_num();
@@ -367,9 +479,10 @@ then right click on User Table to blacklist",
                     ProcessState::LoadedBlacklistedUsers(success_blacklist, failed_blacklist);
                 continue;
             };
-            self.runtime.spawn(async {
+            let cancel = self.cancel_load.clone();
+            self.runtime.spawn(async move {
                 tg_client
-                    .start_process(ProcessStart::LoadBlacklistedUsers(hex_data))
+                    .start_process(ProcessStart::LoadBlacklistedUsers(hex_data, cancel))
                     .await;
             });
         }
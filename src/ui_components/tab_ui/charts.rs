@@ -1,15 +1,46 @@
-use chrono::{Datelike, Days, Duration, Months, NaiveDate, NaiveDateTime, Timelike, Weekday};
-use eframe::egui::{Align, Button, ComboBox, Grid, Key, Layout, RichText, Ui};
+use chrono::{
+    DateTime, Datelike, Days, Duration, Months, NaiveDate, NaiveDateTime, Timelike, Weekday,
+};
+use eframe::egui::ecolor::Hsva;
+use eframe::egui::{
+    Align, Button, Color32, ComboBox, DragValue, Grid, Key, Layout, Modifiers, RichText,
+    ScrollArea, Ui,
+};
 use egui_dropdown::DropDownBox;
 use egui_extras::DatePickerButton;
-use egui_plot::{Bar, BarChart, Legend, Plot, PlotPoint};
-use std::collections::{BTreeMap, BTreeSet, HashMap};
+use egui_plot::{Bar, BarChart, Legend, Line, LineStyle, Plot, PlotPoint};
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+use std::time::Instant;
 use strum::IntoEnumIterator;
 
 use crate::ui_components::processor::{ChartTiming, ChartType, DateNavigator, NavigationType};
-use crate::ui_components::widgets::AnimatedLabel;
+use crate::ui_components::widgets::{show_word_cloud, AnimatedLabel, WordCloudLayout};
 use crate::ui_components::MainWindow;
-use crate::utils::{time_to_string, weekday_num_to_string};
+use crate::utils::{
+    format_compact_number, hour_num_to_string, save_settings, time_to_string, weekday_num_to_string,
+};
+
+/// Smallest time frame in minutes that messages are grouped by. Custom intervals are multiples of it
+const BASE_INTERVAL: u32 = 15;
+/// Custom interval in minutes used until the user picks another one
+const DEFAULT_CUSTOM_INTERVAL: u32 = 360;
+/// Largest custom interval in minutes
+const MAX_CUSTOM_INTERVAL: u32 = 24 * 60;
+/// Largest delay in milliseconds between two chart updates while counting
+const MAX_CHART_REFRESH: u32 = 5000;
+
+/// Words shorter than this are left out of the word cloud
+const MIN_CLOUD_WORD_LEN: usize = 3;
+/// Number of the most used words shown in the word cloud
+const CLOUD_WORDS: usize = 80;
+
+/// Name of the series that shows the whitelisted users' data
+const WHITELIST_DATA: &str = "Show whitelisted data";
+
+/// Count of each user for each time frame
+type TimeData = BTreeMap<NaiveDateTime, HashMap<String, u64>>;
+/// Count of each user for each weekday or hour of day
+type PeriodData = BTreeMap<u8, HashMap<String, u64>>;
 
 #[derive(Default)]
 pub struct ChartsData {
@@ -19,12 +50,42 @@ pub struct ChartsData {
     last_chart_type: ChartType,
     chart_timing: ChartTiming,
     added_to_chart: BTreeSet<String>,
+    /// Users that are excluded from the chart without being blacklisted
+    hidden_users: HashSet<String>,
+    /// Users that were detected as bots while counting
+    bot_users: HashSet<String>,
+    /// Whether the bot users are excluded from the chart
+    skip_bots: bool,
+    /// IDs of the users that were fetched as the admins or the owner of the chat. Checked
+    /// against `user_ids` so users first seen after fetching are matched as well
+    admin_ids: HashSet<i64>,
+    /// Whether the admin users are excluded from the chart
+    skip_admins: bool,
+    /// Series that were removed from the chart by clicking on the legend
+    legend_hidden: BTreeSet<String>,
     button_sizes: HashMap<String, Option<f32>>,
     hourly_message: BTreeMap<NaiveDateTime, HashMap<String, u64>>,
     daily_message: BTreeMap<NaiveDateTime, HashMap<String, u64>>,
     weekly_message: BTreeMap<NaiveDateTime, HashMap<String, u64>>,
     monthly_message: BTreeMap<NaiveDateTime, HashMap<String, u64>>,
+    /// Messages grouped by `BASE_INTERVAL`, regrouped to create the custom interval data
+    quarter_message: BTreeMap<NaiveDateTime, HashMap<String, u64>>,
+    /// Messages grouped by the custom interval
+    custom_message: BTreeMap<NaiveDateTime, HashMap<String, u64>>,
     weekday_message: BTreeMap<u8, HashMap<String, u64>>,
+    /// Messages of each user by the hour of the day, summed across all dates
+    hour_message: BTreeMap<u8, HashMap<String, u64>>,
+    /// Total words sent by each user in each time frame, same keys as the message data
+    hourly_words: BTreeMap<NaiveDateTime, HashMap<String, u64>>,
+    daily_words: BTreeMap<NaiveDateTime, HashMap<String, u64>>,
+    weekly_words: BTreeMap<NaiveDateTime, HashMap<String, u64>>,
+    monthly_words: BTreeMap<NaiveDateTime, HashMap<String, u64>>,
+    quarter_words: BTreeMap<NaiveDateTime, HashMap<String, u64>>,
+    custom_words: BTreeMap<NaiveDateTime, HashMap<String, u64>>,
+    /// Length of each time frame of the custom timing in minutes
+    custom_interval: u32,
+    /// Whether the custom interval data needs to be regrouped before it is shown
+    custom_outdated: bool,
     last_hour: HashMap<String, Option<NaiveDateTime>>,
     last_day: HashMap<String, Option<NaiveDateTime>>,
     last_week: HashMap<String, Option<NaiveDateTime>>,
@@ -41,6 +102,49 @@ pub struct ChartsData {
     hourly_labels: HashMap<i64, (NaiveDateTime, u64, u64)>,
     /// Hover labels for the daily chart, key = x value in chart. values = (date, total message, whitelist message)
     daily_labels: HashMap<i64, (NaiveDateTime, u64, u64)>,
+    /// Pre-calculated insights for the selected date range
+    insights: Option<ChartInsights>,
+    /// Whether to show the bars of the message chart side by side instead of stacking them
+    grouped_bars: bool,
+    /// Whether new messages were added since the chart was last shown
+    data_added: bool,
+    /// Whether new messages were added since the saved bars were last cleared
+    bars_outdated: bool,
+    /// When the saved bars were last cleared because of new messages
+    last_rebuild: Option<Instant>,
+    /// Number of times each word was used in the counted messages
+    word_frequency: HashMap<String, u64>,
+    /// The most used words sorted by count, cleared when new words are added
+    cloud_words: Option<Vec<(String, u64)>>,
+    /// Placed words of the word cloud, cleared with `cloud_words`
+    cloud_layout: Option<WordCloudLayout>,
+}
+
+/// Chart data of a user that was removed by blacklisting, used to restore it on undo
+pub struct RemovedChartUser {
+    name: String,
+    user_id: Option<i64>,
+    available: bool,
+    added_to_chart: bool,
+    legend_hidden: bool,
+    /// Hourly, daily, weekly, monthly and base interval message counts
+    messages: [Vec<(NaiveDateTime, u64)>; 5],
+    /// Hourly, daily, weekly, monthly and base interval word counts
+    words: [Vec<(NaiveDateTime, u64)>; 5],
+    weekday: Vec<(u8, u64)>,
+    hour: Vec<(u8, u64)>,
+}
+
+/// A quick summary of a chat within the selected date range
+#[derive(Clone)]
+struct ChartInsights {
+    /// Hour of the day with the most messages and its message count
+    busiest_hour: Option<(u32, u64)>,
+    /// Weekday with the most messages and its message count
+    busiest_weekday: Option<(u8, u64)>,
+    /// User with the most messages and their message count
+    most_active_user: Option<(String, u64)>,
+    average_per_day: f64,
 }
 
 impl ChartsData {
@@ -49,6 +153,13 @@ impl ChartsData {
         self.available_users.clear();
         self.dropdown_user.clear();
         self.added_to_chart.clear();
+        self.hidden_users.clear();
+        self.bot_users.clear();
+        self.admin_ids.clear();
+        self.word_frequency.clear();
+        self.cloud_words = None;
+        self.cloud_layout = None;
+        self.legend_hidden.clear();
         self.button_sizes.clear();
         self.last_day = HashMap::new();
         self.last_hour = HashMap::new();
@@ -60,6 +171,16 @@ impl ChartsData {
         self.daily_message.clear();
         self.user_ids.clear();
         self.weekday_message.clear();
+        self.hour_message.clear();
+        self.hourly_words.clear();
+        self.daily_words.clear();
+        self.weekly_words.clear();
+        self.monthly_words.clear();
+        self.quarter_message.clear();
+        self.quarter_words.clear();
+        self.custom_message.clear();
+        self.custom_words.clear();
+        self.custom_interval = DEFAULT_CUSTOM_INTERVAL;
         self.reset_saved_bars();
 
         let mut ongoing_value = Some(Weekday::Mon);
@@ -75,22 +196,44 @@ impl ChartsData {
             }
         }
 
+        for hour in 0..24 {
+            self.hour_message.insert(hour, HashMap::new());
+        }
+
         // These two are added to the chart by default
         self.dropdown_user = "Show total data".to_string();
         self.add_to_chart();
-        self.dropdown_user = "Show whitelisted data".to_string();
+        self.dropdown_user = WHITELIST_DATA.to_string();
         self.add_to_chart();
         self.date_nav = DateNavigator::default();
     }
     /// Adds the user specified in the text edit in the chart
     fn add_to_chart(&mut self) {
-        self.added_to_chart.insert(self.dropdown_user.clone());
-        self.available_users.remove(&self.dropdown_user);
-        self.button_sizes.insert(self.dropdown_user.clone(), None);
-        self.dropdown_user.clear();
+        let user = std::mem::take(&mut self.dropdown_user);
+        self.add_user_to_chart(user);
+    }
+
+    fn add_user_to_chart(&mut self, user: String) {
+        self.legend_hidden.remove(&user);
+        self.available_users.remove(&user);
+        self.button_sizes.insert(user.clone(), None);
+        self.added_to_chart.insert(user);
         self.reset_saved_bars();
     }
 
+    fn whitelist_shown(&self) -> bool {
+        self.added_to_chart.contains(WHITELIST_DATA)
+    }
+
+    /// Adds or removes the whitelisted data series from the chart
+    fn toggle_whitelist_data(&mut self) {
+        if self.whitelist_shown() {
+            self.remove_from_chart(WHITELIST_DATA);
+        } else {
+            self.add_user_to_chart(WHITELIST_DATA.to_string());
+        }
+    }
+
     /// Removes the user that was clicked on from the chart
     fn remove_from_chart(&mut self, user: &str) {
         self.added_to_chart.remove(user);
@@ -98,8 +241,22 @@ impl ChartsData {
         self.reset_saved_bars();
     }
 
+    /// Removes a series from the chart or adds it back after it was clicked on the legend
+    fn toggle_legend_entry(&mut self, key: &str) {
+        if self.added_to_chart.contains(key) {
+            self.remove_from_chart(key);
+            self.legend_hidden.insert(key.to_string());
+        } else {
+            self.dropdown_user = key.to_string();
+            self.add_to_chart();
+        }
+    }
+
     /// Adds a user available for adding in the chart
-    pub fn add_user(&mut self, user: String, user_id: i64) {
+    pub fn add_user(&mut self, user: String, user_id: i64, is_bot: bool) {
+        if is_bot {
+            self.bot_users.insert(user.clone());
+        }
         self.available_users.insert(user.clone());
         self.user_ids.insert(user, user_id);
     }
@@ -110,27 +267,17 @@ impl ChartsData {
         time: NaiveDateTime,
         date: NaiveDate,
         add_to: String,
+        word_count: u64,
         client_name: &str,
     ) {
+        self.data_added = true;
+
         // keep a common value among messages for example messages sent within the same hour,
-        // reset the second and minute value to 0 so these messages can be grouped
-        let hourly_time = time.with_second(0).unwrap().with_minute(0).unwrap();
-        let daily_time = time
-            .with_second(0)
-            .unwrap()
-            .with_minute(0)
-            .unwrap()
-            .with_hour(0)
-            .unwrap();
-        let monthly_time = time
-            .with_second(0)
-            .unwrap()
-            .with_minute(0)
-            .unwrap()
-            .with_hour(0)
-            .unwrap()
-            .with_day(1)
-            .unwrap();
+        // round the time down to the start of the time frame so these messages can be grouped
+        let quarter_time = round_to_interval(time, BASE_INTERVAL);
+        let hourly_time = round_to_interval(time, 60);
+        let daily_time = round_to_interval(time, 24 * 60);
+        let monthly_time = daily_time.with_day(1).unwrap();
 
         let sent_on = time.weekday();
 
@@ -226,53 +373,541 @@ impl ChartsData {
         let target_user = counter.entry(add_to.clone()).or_insert(0);
         *target_user += 1;
 
+        let counter = self.quarter_message.entry(quarter_time).or_default();
+        let target_user = counter.entry(add_to.clone()).or_insert(0);
+        *target_user += 1;
+
+        *self
+            .hourly_words
+            .entry(hourly_time)
+            .or_default()
+            .entry(add_to.clone())
+            .or_insert(0) += word_count;
+        *self
+            .daily_words
+            .entry(daily_time)
+            .or_default()
+            .entry(add_to.clone())
+            .or_insert(0) += word_count;
+        *self
+            .weekly_words
+            .entry(weekly_time)
+            .or_default()
+            .entry(add_to.clone())
+            .or_insert(0) += word_count;
+        *self
+            .monthly_words
+            .entry(monthly_time)
+            .or_default()
+            .entry(add_to.clone())
+            .or_insert(0) += word_count;
+        *self
+            .quarter_words
+            .entry(quarter_time)
+            .or_default()
+            .entry(add_to.clone())
+            .or_insert(0) += word_count;
+
         let counter = self.weekday_message.get_mut(&(sent_on as u8)).unwrap();
+        let target_user = counter.entry(add_to.clone()).or_insert(0);
+        *target_user += 1;
+
+        let counter = self.hour_message.get_mut(&(time.hour() as u8)).unwrap();
         let target_user = counter.entry(add_to).or_insert(0);
         *target_user += 1;
 
-        self.reset_saved_bars();
+        // The saved bars are cleared with a delay so they are not rebuilt on every message
+        self.bars_outdated = true;
         self.date_nav.handler().update_dates(date);
     }
 
+    /// Clears the saved bars if new messages were added and at least the given interval has
+    /// passed since the last time. Returns the time left until the next rebuild, if any
+    fn refresh_outdated_bars(
+        &mut self,
+        interval: std::time::Duration,
+    ) -> Option<std::time::Duration> {
+        if !self.bars_outdated {
+            return None;
+        }
+
+        let elapsed = self.last_rebuild.map(|time| time.elapsed());
+        if let Some(elapsed) = elapsed.filter(|elapsed| *elapsed < interval) {
+            return Some(interval - elapsed);
+        }
+
+        self.reset_saved_bars();
+        self.bars_outdated = false;
+        self.last_rebuild = Some(Instant::now());
+        None
+    }
+
     /// Clears all pre-saved bars
     pub fn reset_saved_bars(&mut self) {
+        self.insights = None;
+        self.custom_outdated = true;
         self.hourly_bars = None;
         self.daily_bars = None;
         self.hourly_labels.clear();
         self.daily_labels.clear();
     }
 
+    /// Message and word counts of each user for each key of the given timing
+    fn timing_data(&self, timing: ChartTiming) -> (&TimeData, &TimeData) {
+        match timing {
+            ChartTiming::Hourly => (&self.hourly_message, &self.hourly_words),
+            ChartTiming::Daily => (&self.daily_message, &self.daily_words),
+            ChartTiming::Weekly => (&self.weekly_message, &self.weekly_words),
+            ChartTiming::Monthly => (&self.monthly_message, &self.monthly_words),
+            ChartTiming::Custom => (&self.custom_message, &self.custom_words),
+        }
+    }
+
+    /// Change the length of the custom time frames in minutes
+    fn set_custom_interval(&mut self, minutes: u32) {
+        if self.custom_interval != minutes {
+            self.custom_interval = minutes;
+            self.reset_saved_bars();
+        }
+    }
+
+    /// Regroups the base interval data by the custom interval if it has changed since the last
+    /// time it was grouped
+    fn prepare_custom_data(&mut self) {
+        if !self.custom_outdated {
+            return;
+        }
+        self.custom_outdated = false;
+
+        let interval = self.custom_interval.max(BASE_INTERVAL);
+        self.custom_message = regroup_by_interval(&self.quarter_message, interval);
+        self.custom_words = regroup_by_interval(&self.quarter_words, interval);
+        fill_missing_time(&mut self.custom_message, |time| {
+            time + Duration::minutes(i64::from(interval))
+        });
+    }
+
+    /// Total messages of the users that are not hidden for each key of the given timing
+    fn total_messages(&self, timing: ChartTiming) -> BTreeMap<NaiveDateTime, u64> {
+        let (message_map, _) = self.timing_data(timing);
+
+        message_map
+            .iter()
+            .map(|(key, users)| {
+                let total = users
+                    .iter()
+                    .filter(|(user, _)| !self.is_hidden(user))
+                    .map(|(_, count)| count)
+                    .sum();
+                (*key, total)
+            })
+            .collect()
+    }
+
+    /// Adds the words of a message to the word frequency. Links, mentions and words without
+    /// any letter are skipped
+    pub fn add_words(&mut self, text: &str) {
+        for word in text.split_whitespace() {
+            if word.contains("://") || word.starts_with('@') || word.starts_with('/') {
+                continue;
+            }
+
+            let word = word.trim_matches(|c: char| !c.is_alphanumeric());
+            if word.chars().count() < MIN_CLOUD_WORD_LEN || !word.chars().any(char::is_alphabetic) {
+                continue;
+            }
+
+            *self.word_frequency.entry(word.to_lowercase()).or_default() += 1;
+            self.cloud_words = None;
+            self.cloud_layout = None;
+        }
+    }
+
+    /// The most used words sorted by count, highest first, and the saved placement of them
+    fn word_cloud(&mut self) -> (&[(String, u64)], &mut Option<WordCloudLayout>) {
+        let words = self.cloud_words.get_or_insert_with(|| {
+            let mut words: Vec<(String, u64)> = self
+                .word_frequency
+                .iter()
+                .map(|(word, count)| (word.clone(), *count))
+                .collect();
+            words.sort_unstable_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+            words.truncate(CLOUD_WORDS);
+            words
+        });
+        (words, &mut self.cloud_layout)
+    }
+
+    /// Total messages of each user that is not hidden within the selected date range
+    fn user_message_totals(&self) -> Vec<u64> {
+        let handler = self.date_nav.handler_i();
+        let mut user_totals: HashMap<&String, u64> = HashMap::new();
+
+        for (key, users) in &self.daily_message {
+            if !handler.within_range(key.date()) {
+                continue;
+            }
+            for (user, count) in users {
+                if *count == 0 || self.is_hidden(user) {
+                    continue;
+                }
+                *user_totals.entry(user).or_default() += count;
+            }
+        }
+
+        user_totals.into_values().collect()
+    }
+
+    /// Returns the insights for the selected date range, calculating them if necessary
+    fn insights(&mut self) -> ChartInsights {
+        if let Some(insights) = &self.insights {
+            return insights.clone();
+        }
+
+        let mut hour_count: HashMap<u32, u64> = HashMap::new();
+        let mut weekday_count: HashMap<u8, u64> = HashMap::new();
+        let mut user_count: HashMap<&String, u64> = HashMap::new();
+        let mut total_message = 0;
+
+        let handler = self.date_nav.handler_i();
+
+        for (time, users) in &self.hourly_message {
+            let key_date = time.date();
+            if !handler.within_range(key_date) {
+                if handler.before_to_range(key_date) {
+                    continue;
+                }
+                break;
+            }
+
+            let message_count: u64 = users
+                .iter()
+                .filter(|(user, _)| !self.is_hidden(user))
+                .map(|(_, count)| count)
+                .sum();
+            if message_count == 0 {
+                continue;
+            }
+
+            total_message += message_count;
+            *hour_count.entry(time.hour()).or_default() += message_count;
+            *weekday_count
+                .entry(time.weekday().num_days_from_monday() as u8)
+                .or_default() += message_count;
+
+            for (user, count) in users {
+                if !self.is_hidden(user) {
+                    *user_count.entry(user).or_default() += count;
+                }
+            }
+        }
+
+        let total_days = (handler.to - handler.from).num_days() + 1;
+        let average_per_day = total_message as f64 / total_days.max(1) as f64;
+
+        let insights = ChartInsights {
+            busiest_hour: hour_count.into_iter().max_by_key(|(_, count)| *count),
+            busiest_weekday: weekday_count.into_iter().max_by_key(|(_, count)| *count),
+            most_active_user: user_count
+                .into_iter()
+                .max_by_key(|(_, count)| *count)
+                .map(|(user, count)| (user.to_string(), count)),
+            average_per_day,
+        };
+
+        self.insights = Some(insights.clone());
+        insights
+    }
+
+    /// The day with the most messages and its message count
+    pub fn busiest_day(&self) -> Option<(NaiveDate, u64)> {
+        self.daily_message
+            .iter()
+            .map(|(time, users)| (time.date(), users.values().sum::<u64>()))
+            .filter(|(_, count)| *count > 0)
+            .max_by_key(|(_, count)| *count)
+    }
+
+    /// The data of the selected weekday or hour of day chart and the function to name its keys
+    fn period_data(&self) -> (&PeriodData, fn(u8) -> String) {
+        if self.chart_type == ChartType::MessageHourOfDay
+            || self.chart_type == ChartType::ActiveUserHourOfDay
+        {
+            (&self.hour_message, hour_num_to_string)
+        } else {
+            (&self.weekday_message, weekday_num_to_string)
+        }
+    }
+
     /// Whether total message and whitelist message are added to the chart
     fn message_whitelist_added(&self, row_len: usize) -> (bool, bool) {
         // If there is no whitelisted users, this will be considered as not-shown. Adds extra bars
         // to the ui => consume more power.
-        let whitelist = self.added_to_chart.contains("Show whitelisted data") && row_len > 0;
+        let whitelist = self.whitelist_shown() && row_len > 0;
 
         (self.added_to_chart.contains("Show total data"), whitelist)
     }
 
-    pub fn clear_blacklisted(&mut self, names: &[String]) {
+    /// Adds all the chart data of another chat into this chart
+    pub fn merge_from(&mut self, other: &ChartsData) {
+        merge_message_map(&mut self.hourly_message, &other.hourly_message);
+        merge_message_map(&mut self.daily_message, &other.daily_message);
+        merge_message_map(&mut self.weekly_message, &other.weekly_message);
+        merge_message_map(&mut self.monthly_message, &other.monthly_message);
+        merge_message_map(&mut self.weekday_message, &other.weekday_message);
+        merge_message_map(&mut self.hour_message, &other.hour_message);
+        merge_message_map(&mut self.hourly_words, &other.hourly_words);
+        merge_message_map(&mut self.daily_words, &other.daily_words);
+        merge_message_map(&mut self.weekly_words, &other.weekly_words);
+        merge_message_map(&mut self.monthly_words, &other.monthly_words);
+        merge_message_map(&mut self.quarter_message, &other.quarter_message);
+        merge_message_map(&mut self.quarter_words, &other.quarter_words);
+
+        // The two chats may not be continuous. Fill the gap between them with 0 value
+        self.fill_missing_times();
+
+        self.bot_users.extend(other.bot_users.iter().cloned());
+        self.admin_ids.extend(&other.admin_ids);
+        for (word, count) in &other.word_frequency {
+            *self.word_frequency.entry(word.clone()).or_default() += count;
+        }
+        self.cloud_words = None;
+        self.cloud_layout = None;
+        for (user, user_id) in &other.user_ids {
+            if !self.added_to_chart.contains(user) {
+                self.available_users.insert(user.clone());
+            }
+            self.user_ids.insert(user.clone(), *user_id);
+        }
+
+        self.date_nav
+            .handler()
+            .merge_dates(other.date_nav.handler_i());
+        self.reset_saved_bars();
+    }
+
+    /// Hide or show a user in the chart
+    fn toggle_hidden_user(&mut self, user: &str) {
+        if !self.hidden_users.remove(user) {
+            self.hidden_users.insert(user.to_string());
+        }
+        self.reset_saved_bars();
+    }
+
+    /// Whether the user is excluded from the chart either manually or for being a bot
+    fn is_hidden(&self, user: &str) -> bool {
+        self.hidden_users.contains(user)
+            || (self.skip_bots && self.bot_users.contains(user))
+            || (self.skip_admins
+                && self
+                    .user_ids
+                    .get(user)
+                    .is_some_and(|user_id| self.admin_ids.contains(user_id)))
+    }
+
+    /// Exclude or include the bot users in the chart
+    pub fn set_skip_bots(&mut self, skip_bots: bool) {
+        if self.skip_bots != skip_bots {
+            self.skip_bots = skip_bots;
+            self.reset_saved_bars();
+        }
+    }
+
+    /// Exclude or include the admin users in the chart
+    pub fn set_skip_admins(&mut self, skip_admins: bool) {
+        if self.skip_admins != skip_admins {
+            self.skip_admins = skip_admins;
+            self.reset_saved_bars();
+        }
+    }
+
+    /// Marks the users with the given IDs as the admins of the chat
+    pub fn set_admins(&mut self, admin_ids: &HashSet<i64>) {
+        self.admin_ids.clone_from(admin_ids);
+        self.reset_saved_bars();
+    }
+
+    /// Fills every gap in the chart data with 0 value
+    pub fn fill_missing_times(&mut self) {
+        fill_missing_time(&mut self.hourly_message, |time| time + Duration::hours(1));
+        fill_missing_time(&mut self.daily_message, |time| time + Duration::days(1));
+        fill_missing_time(&mut self.weekly_message, |time| time + Duration::weeks(1));
+        fill_missing_time(&mut self.monthly_message, |time| {
+            time.checked_add_months(Months::new(1)).unwrap()
+        });
+    }
+
+    /// Removes all data of the given users and returns the removed data
+    pub fn clear_blacklisted(&mut self, names: &[String]) -> Vec<RemovedChartUser> {
+        let mut removed = Vec::new();
         for n in names {
-            self.available_users.remove(n);
-            self.added_to_chart.remove(n);
-            self.hourly_message.iter_mut().for_each(|(_d, data)| {
-                data.remove(n);
-            });
-            self.daily_message.iter_mut().for_each(|(_d, data)| {
-                data.remove(n);
-            });
-            self.weekly_message.iter_mut().for_each(|(_d, data)| {
-                data.remove(n);
-            });
-            self.monthly_message.iter_mut().for_each(|(_d, data)| {
-                data.remove(n);
-            });
-            self.weekday_message.iter_mut().for_each(|(_d, data)| {
-                data.remove(n);
-            });
-            self.user_ids.remove(n);
+            let mut removed_user = RemovedChartUser {
+                name: n.clone(),
+                user_id: self.user_ids.remove(n),
+                available: self.available_users.remove(n),
+                added_to_chart: self.added_to_chart.remove(n),
+                legend_hidden: self.legend_hidden.remove(n),
+                messages: Default::default(),
+                words: Default::default(),
+                weekday: take_user_counts(&mut self.weekday_message, n),
+                hour: take_user_counts(&mut self.hour_message, n),
+            };
+
+            for (index, messages) in [
+                &mut self.hourly_message,
+                &mut self.daily_message,
+                &mut self.weekly_message,
+                &mut self.monthly_message,
+                &mut self.quarter_message,
+            ]
+            .into_iter()
+            .enumerate()
+            {
+                removed_user.messages[index] = take_user_counts(messages, n);
+            }
+
+            for (index, words) in [
+                &mut self.hourly_words,
+                &mut self.daily_words,
+                &mut self.weekly_words,
+                &mut self.monthly_words,
+                &mut self.quarter_words,
+            ]
+            .into_iter()
+            .enumerate()
+            {
+                removed_user.words[index] = take_user_counts(words, n);
+            }
+            removed.push(removed_user);
         }
         self.reset_saved_bars();
+        removed
+    }
+
+    /// Adds back the data of users that were removed by blacklisting
+    pub fn restore_blacklisted(&mut self, users: Vec<RemovedChartUser>) {
+        for user in users {
+            let name = user.name;
+            if let Some(user_id) = user.user_id {
+                self.user_ids.insert(name.clone(), user_id);
+            }
+            if user.available {
+                self.available_users.insert(name.clone());
+            }
+            if user.added_to_chart {
+                self.added_to_chart.insert(name.clone());
+            }
+            if user.legend_hidden {
+                self.legend_hidden.insert(name.clone());
+            }
+            restore_user_counts(&mut self.weekday_message, &name, user.weekday);
+            restore_user_counts(&mut self.hour_message, &name, user.hour);
+
+            let [hourly, daily, weekly, monthly, quarter] = user.messages;
+            restore_user_counts(&mut self.hourly_message, &name, hourly);
+            restore_user_counts(&mut self.daily_message, &name, daily);
+            restore_user_counts(&mut self.weekly_message, &name, weekly);
+            restore_user_counts(&mut self.monthly_message, &name, monthly);
+            restore_user_counts(&mut self.quarter_message, &name, quarter);
+
+            let [hourly, daily, weekly, monthly, quarter] = user.words;
+            restore_user_counts(&mut self.hourly_words, &name, hourly);
+            restore_user_counts(&mut self.daily_words, &name, daily);
+            restore_user_counts(&mut self.weekly_words, &name, weekly);
+            restore_user_counts(&mut self.monthly_words, &name, monthly);
+            restore_user_counts(&mut self.quarter_words, &name, quarter);
+        }
+        self.reset_saved_bars();
+    }
+}
+
+/// Removes the count of the user from every key and returns the removed counts
+fn take_user_counts<K: Ord + Copy>(
+    map: &mut BTreeMap<K, HashMap<String, u64>>,
+    user: &str,
+) -> Vec<(K, u64)> {
+    map.iter_mut()
+        .filter_map(|(key, data)| data.remove(user).map(|count| (*key, count)))
+        .collect()
+}
+
+/// Adds the counts of the user back to their keys
+fn restore_user_counts<K: Ord + Copy>(
+    map: &mut BTreeMap<K, HashMap<String, u64>>,
+    user: &str,
+    counts: Vec<(K, u64)>,
+) {
+    for (key, count) in counts {
+        *map.entry(key)
+            .or_default()
+            .entry(user.to_string())
+            .or_insert(0) += count;
+    }
+}
+
+/// Color of the nth series of the chart, following the same order `egui_plot` uses
+fn series_color(index: usize) -> Color32 {
+    let golden_ratio = (5.0_f32.sqrt() - 1.0) / 2.0;
+    let hue = index as f32 * golden_ratio;
+    Hsva::new(hue, 0.85, 0.5, 1.0).into()
+}
+
+/// Sums up the message count of each user for each key of `from` into `to`
+fn merge_message_map<K: Ord + Copy>(
+    to: &mut BTreeMap<K, HashMap<String, u64>>,
+    from: &BTreeMap<K, HashMap<String, u64>>,
+) {
+    for (key, users) in from {
+        let counter = to.entry(*key).or_default();
+        for (user, count) in users {
+            *counter.entry(user.clone()).or_insert(0) += count;
+        }
+    }
+}
+
+/// Rounds the time down to the start of its time frame. Time frames are counted from the Unix
+/// epoch so every interval that divides a day starts at midnight
+fn round_to_interval(time: NaiveDateTime, minutes: u32) -> NaiveDateTime {
+    let interval = i64::from(minutes.max(1)) * 60;
+    let timestamp = time.and_utc().timestamp();
+    DateTime::from_timestamp(timestamp - timestamp.rem_euclid(interval), 0)
+        .unwrap()
+        .naive_utc()
+}
+
+/// Sums up the counts of each user into time frames of the given interval
+fn regroup_by_interval(
+    message_map: &BTreeMap<NaiveDateTime, HashMap<String, u64>>,
+    minutes: u32,
+) -> BTreeMap<NaiveDateTime, HashMap<String, u64>> {
+    let mut grouped: BTreeMap<NaiveDateTime, HashMap<String, u64>> = BTreeMap::new();
+    for (time, users) in message_map {
+        let counter = grouped
+            .entry(round_to_interval(*time, minutes))
+            .or_default();
+        for (user, count) in users {
+            *counter.entry(user.clone()).or_insert(0) += count;
+        }
+    }
+    grouped
+}
+
+/// Adds every missing time between the oldest and the newest key with no data
+fn fill_missing_time(
+    message_map: &mut BTreeMap<NaiveDateTime, HashMap<String, u64>>,
+    next_time: impl Fn(NaiveDateTime) -> NaiveDateTime,
+) {
+    let (Some(first), Some(last)) = (
+        message_map.keys().next().copied(),
+        message_map.keys().next_back().copied(),
+    ) else {
+        return;
+    };
+
+    let mut ongoing_time = next_time(first);
+    while ongoing_time < last {
+        message_map.entry(ongoing_time).or_default();
+        ongoing_time = next_time(ongoing_time);
     }
 }
 
@@ -296,10 +931,57 @@ impl MainWindow {
                 len,
                 |i| &values[i],
             );
+            ui.separator();
+            if ui
+                .checkbox(&mut self.settings.auto_fit_chart, "Auto-fit while counting")
+                .on_hover_text(
+                    "Reset the zoom of the chart to fit all data whenever new messages are counted",
+                )
+                .changed()
+            {
+                save_settings(&self.settings);
+            }
+            ui.label("Refresh every:");
+            if ui
+                .add(
+                    DragValue::new(&mut self.settings.chart_refresh.0)
+                        .range(0..=MAX_CHART_REFRESH)
+                        .speed(50)
+                        .suffix(" ms"),
+                )
+                .on_hover_text("Minimum time between two chart updates while counting. Higher values use less CPU")
+                .changed()
+            {
+                save_settings(&self.settings);
+            }
+            ui.separator();
+            if ui
+                .selectable_label(self.chart_i().whitelist_shown(), "Show whitelist overlay")
+                .on_hover_text(
+                    "Show the data of the whitelisted users on top of the total data. Shortcut key: CTRL + W",
+                )
+                .clicked()
+            {
+                self.chart().toggle_whitelist_data();
+            }
         });
         ui.separator();
-        let not_weekday_chart = self.chart_i().chart_type != ChartType::MessageWeekDay
-            && self.chart_i().chart_type != ChartType::ActiveUserWeekDay;
+
+        if self.is_count_only(self.chart_chat_index) {
+            ui.label("Disabled for this count. The chat was counted without per-user stats");
+            return;
+        }
+
+        if ui
+            .ctx()
+            .input_mut(|i| i.consume_key(Modifiers::COMMAND, Key::W))
+        {
+            self.chart().toggle_whitelist_data();
+        }
+
+        let not_weekday_chart = !self.chart_i().chart_type.is_period()
+            && self.chart_i().chart_type != ChartType::Concentration
+            && self.chart_i().chart_type != ChartType::WordCloud;
 
         ui.horizontal(|ui| {
             ui.selectable_value(
@@ -314,6 +996,18 @@ impl MainWindow {
                 ChartType::ActiveUser.to_string(),
             ).on_hover_text("Chart showing the total count of active users in the selected time frame (e.g., hourly, daily, weekly).");
             ui.separator();
+            ui.selectable_value(
+                &mut self.chart().chart_type,
+                ChartType::CumulativeUser,
+                ChartType::CumulativeUser.to_string(),
+            ).on_hover_text("Chart showing the total count of distinct users that have sent at least 1 message up to each time frame, starting from the selected From date.");
+            ui.separator();
+            ui.selectable_value(
+                &mut self.chart().chart_type,
+                ChartType::AvgWordsOverTime,
+                ChartType::AvgWordsOverTime.to_string(),
+            ).on_hover_text("Chart showing the average words per message in the selected time frame (e.g., hourly, daily, weekly).");
+            ui.separator();
             ui.selectable_value(
                 &mut self.chart().chart_type,
                 ChartType::MessageWeekDay,
@@ -325,6 +1019,36 @@ impl MainWindow {
                 ChartType::ActiveUserWeekDay,
                 ChartType::ActiveUserWeekDay.to_string(),
             ).on_hover_text("Chart displaying the total count of active users for each day of the week.");
+            ui.separator();
+            ui.selectable_value(
+                &mut self.chart().chart_type,
+                ChartType::MessageHourOfDay,
+                ChartType::MessageHourOfDay.to_string(),
+            ).on_hover_text("Chart showing the total count of messages in each hour of the day across all dates.");
+            ui.separator();
+            ui.selectable_value(
+                &mut self.chart().chart_type,
+                ChartType::ActiveUserHourOfDay,
+                ChartType::ActiveUserHourOfDay.to_string(),
+            ).on_hover_text("Chart displaying the total count of active users in each hour of the day across all dates.");
+            ui.separator();
+            ui.selectable_value(
+                &mut self.chart().chart_type,
+                ChartType::ChatComparison,
+                ChartType::ChatComparison.to_string(),
+            ).on_hover_text("Chart comparing the total count of messages of all counted chats in the selected time frame, using the date range of the selected chat.");
+            ui.separator();
+            ui.selectable_value(
+                &mut self.chart().chart_type,
+                ChartType::Concentration,
+                ChartType::Concentration.to_string(),
+            ).on_hover_text("Chart showing the cumulative share of messages against the cumulative share of users, sorted from the least active user. The further the curve is from the diagonal, the more a few users dominate the chat.");
+            ui.separator();
+            ui.selectable_value(
+                &mut self.chart().chart_type,
+                ChartType::WordCloud,
+                ChartType::WordCloud.to_string(),
+            ).on_hover_text("The most used words across all counted messages of the chat, sized by how often they were used. Short words, links and mentions are left out.");
         });
         if not_weekday_chart {
             ui.separator();
@@ -352,6 +1076,36 @@ impl MainWindow {
                     ChartTiming::Monthly,
                     "Monthly",
                 );
+                ui.separator();
+                ui.selectable_value(
+                    &mut self.chart().chart_timing,
+                    ChartTiming::Custom,
+                    ChartTiming::Custom.to_string(),
+                )
+                .on_hover_text("Group the messages by a custom number of minutes");
+
+                if self.chart_i().chart_timing == ChartTiming::Custom {
+                    let mut interval = self.chart_i().custom_interval;
+                    ui.add(
+                        DragValue::new(&mut interval)
+                            .range(BASE_INTERVAL..=MAX_CUSTOM_INTERVAL)
+                            .speed(BASE_INTERVAL)
+                            .suffix(" min"),
+                    )
+                    .on_hover_text(format!(
+                        "Length of each time frame in minutes. Rounded to a multiple of {BASE_INTERVAL}"
+                    ));
+                    let interval = interval / BASE_INTERVAL * BASE_INTERVAL;
+                    self.chart().set_custom_interval(interval);
+                }
+
+                if self.chart_i().chart_type == ChartType::Message {
+                    ui.separator();
+                    ui.selectable_value(&mut self.chart().grouped_bars, false, "Stacked")
+                        .on_hover_text("Stack the bars of each data on top of each other");
+                    ui.selectable_value(&mut self.chart().grouped_bars, true, "Grouped")
+                        .on_hover_text("Show the bars of each data side by side");
+                }
             });
             ui.separator();
         } else {
@@ -376,6 +1130,8 @@ impl MainWindow {
                             self.chart().add_to_chart();
                         };
 
+                        self.show_hidden_users_menu(ui);
+
                         let available_users = self.chart_i().available_users.clone();
                         ui.add_sized(
                             ui.available_size(),
@@ -521,6 +1277,17 @@ impl MainWindow {
                     if ui.button(format!("Next {}", chart.date_nav.nav_name())).on_hover_text(next_hover).clicked() {
                         chart.date_nav.go_next();
                     };
+
+                    ui.separator();
+
+                    ui.label("Jump to:");
+                    let center_date = chart.date_nav.center_date();
+                    let mut jump_date = center_date;
+                    ui.add(DatePickerButton::new(&mut jump_date).id_salt("3"))
+                        .on_hover_text(format!("Show 1 {} of data centered on the chosen date", chart.date_nav.nav_name()));
+                    if jump_date != center_date {
+                        chart.date_nav.jump_to(jump_date);
+                    }
                 });
             });
 
@@ -552,11 +1319,19 @@ impl MainWindow {
             ui.label("Use CTRL + scroll to zoom, drag mouse or scroll to move and double click to fit/reset the chart");
         });
 
+        let refresh_interval = self.settings.chart_refresh.interval();
+        if let Some(remaining) = self.chart().refresh_outdated_bars(refresh_interval) {
+            ui.ctx().request_repaint_after(remaining);
+        }
+
+        self.show_insights(ui);
+
         let current_type = &self.chart_i().chart_type;
         let last_type = &self.chart_i().last_chart_type;
 
-        let is_message_user =
-            current_type == &ChartType::Message || current_type == &ChartType::ActiveUser;
+        let is_message_user = current_type == &ChartType::Message
+            || current_type == &ChartType::ActiveUser
+            || current_type == &ChartType::CumulativeUser;
 
         // We do not care about changes in other timing as only these two are saved
         // If the last time Message type was selected, and currently it's user, reset saved bar
@@ -566,12 +1341,129 @@ impl MainWindow {
             self.chart().reset_saved_bars();
         }
 
+        if self.chart_i().chart_timing == ChartTiming::Custom {
+            self.chart().prepare_custom_data();
+        }
+
         match self.chart().chart_type {
             ChartType::Message => self.display_message_chart(ui),
-            ChartType::ActiveUser => self.display_active_user_chart(ui),
-            ChartType::MessageWeekDay => self.display_weekday_message_chart(ui),
-            ChartType::ActiveUserWeekDay => self.display_weekday_active_user_chart(ui),
+            ChartType::ActiveUser | ChartType::CumulativeUser => {
+                self.display_active_user_chart(ui);
+            }
+            ChartType::AvgWordsOverTime => self.display_avg_words_chart(ui),
+            ChartType::MessageWeekDay | ChartType::MessageHourOfDay => {
+                self.display_period_message_chart(ui);
+            }
+            ChartType::ActiveUserWeekDay | ChartType::ActiveUserHourOfDay => {
+                self.display_period_active_user_chart(ui);
+            }
+            ChartType::ChatComparison => self.display_chat_comparison_chart(ui),
+            ChartType::Concentration => self.display_concentration_chart(ui),
+            ChartType::WordCloud => {
+                let (words, layout) = self.chart().word_cloud();
+                show_word_cloud(ui, words, layout, series_color);
+            }
+        }
+    }
+
+    /// Shows the series of the chart. Clicking on one removes it from the chart or adds it back
+    fn show_chart_legend(
+        &mut self,
+        ui: &mut Ui,
+        all_series: &[(String, Vec<Bar>, Color32)],
+        series_name: impl Fn(&str) -> String,
+    ) {
+        let mut to_toggle = None;
+
+        ui.horizontal_wrapped(|ui| {
+            for (key, _, color) in all_series {
+                let text = RichText::new(format!("⏺ {}", series_name(key))).color(*color);
+                if ui
+                    .selectable_label(true, text)
+                    .on_hover_text("Click to hide from the chart")
+                    .clicked()
+                {
+                    to_toggle = Some(key.clone());
+                }
+            }
+
+            for key in &self.chart_i().legend_hidden {
+                let text = RichText::new(format!("⏺ {}", series_name(key))).weak();
+                if ui
+                    .selectable_label(false, text)
+                    .on_hover_text("Click to show on the chart")
+                    .clicked()
+                {
+                    to_toggle = Some(key.clone());
+                }
+            }
+        });
+
+        if let Some(key) = to_toggle {
+            self.chart().toggle_legend_entry(&key);
+        }
+    }
+
+    /// Shows a menu to hide users from the chart without blacklisting them
+    fn show_hidden_users_menu(&mut self, ui: &mut Ui) {
+        let total_hidden = self.chart_i().hidden_users.len();
+        ui.menu_button(format!("Hidden Users ({total_hidden})"), |ui| {
+            let all_users: BTreeSet<String> = self.chart_i().user_ids.keys().cloned().collect();
+            let mut to_toggle = None;
+
+            ScrollArea::vertical().max_height(300.0).show(ui, |ui| {
+                for user in &all_users {
+                    let mut hidden = self.chart_i().hidden_users.contains(user);
+                    if ui.checkbox(&mut hidden, user).changed() {
+                        to_toggle = Some(user.clone());
+                    }
+                }
+            });
+
+            if let Some(user) = to_toggle {
+                self.chart().toggle_hidden_user(&user);
+            }
+        })
+        .response
+        .on_hover_text("Exclude users from the chart and its totals without blacklisting them");
+    }
+
+    /// Shows the busiest hour, weekday, most active user and average messages per day
+    fn show_insights(&mut self, ui: &mut Ui) {
+        if self.chart_i().hourly_message.is_empty() {
+            return;
         }
+
+        let insights = self.chart().insights();
+
+        ui.horizontal(|ui| {
+            let busiest_hour = insights
+                .busiest_hour
+                .map_or(String::from("-"), |(hour, count)| {
+                    format!("{hour:02}:00 - {:02}:00 ({count})", (hour + 1) % 24)
+                });
+            ui.label(format!("Busiest Hour: {busiest_hour}"));
+            ui.separator();
+
+            let busiest_weekday = insights
+                .busiest_weekday
+                .map_or(String::from("-"), |(weekday, count)| {
+                    format!("{} ({count})", weekday_num_to_string(weekday))
+                });
+            ui.label(format!("Busiest Day: {busiest_weekday}"));
+            ui.separator();
+
+            let most_active = insights
+                .most_active_user
+                .map_or(String::from("-"), |(user, count)| {
+                    format!("{user} ({count})")
+                });
+            ui.label(format!("Most Active: {most_active}"));
+            ui.separator();
+
+            ui.label(format!("Average Per Day: {:.2}", insights.average_per_day));
+        });
+        ui.separator();
     }
 
     fn display_message_chart(&mut self, ui: &mut Ui) {
@@ -602,12 +1494,8 @@ impl MainWindow {
             return;
         }
 
-        let to_iter = match self.chart_i().chart_timing {
-            ChartTiming::Hourly => self.chart_i().hourly_message.iter().enumerate(),
-            ChartTiming::Daily => self.chart_i().daily_message.iter().enumerate(),
-            ChartTiming::Weekly => self.chart_i().weekly_message.iter().enumerate(),
-            ChartTiming::Monthly => self.chart_i().monthly_message.iter().enumerate(),
-        };
+        let (message_map, _) = self.chart_i().timing_data(self.chart_i().chart_timing);
+        let to_iter = message_map.iter().enumerate();
 
         // Key = The common time where one or more message may have been sent
         // user = All users that sent messages to this common time + the amount of message
@@ -639,10 +1527,11 @@ impl MainWindow {
             // In case a common time does not include a user that is added in the chart
             // add a 0 value bar
             for i in &self.chart_i().added_to_chart {
-                if !user.contains_key(i) && i != "Show total data" && i != "Show whitelisted data" {
+                let no_data = !user.contains_key(i) || self.chart_i().is_hidden(i);
+                if no_data && i != "Show total data" && i != WHITELIST_DATA {
                     let bar = Bar::new(arg, 0.0).name(format!(
                         "{} {i}",
-                        time_to_string(key, self.chart_i().chart_timing)
+                        time_to_string(key, self.chart_i().chart_timing, self.settings.date_format)
                     ));
                     let bar_value = bar_list.entry(i.to_owned()).or_insert(Vec::new());
 
@@ -652,6 +1541,10 @@ impl MainWindow {
 
             // Go through all the users that sent message in this common time and create a bar if necessary
             for (user_name, num) in user {
+                if self.chart_i().is_hidden(user_name) {
+                    continue;
+                }
+
                 if show_whitelisted_message {
                     let is_whitelisted = self
                         .whitelist
@@ -671,7 +1564,7 @@ impl MainWindow {
                 if user_in_chart {
                     let user_bar = Bar::new(arg, num.to_owned() as f64).name(format!(
                         "{} {user_name}",
-                        time_to_string(key, self.chart_i().chart_timing)
+                        time_to_string(key, self.chart_i().chart_timing, self.settings.date_format)
                     ));
                     let bar_value = bar_list.entry(user_name.to_owned()).or_insert(Vec::new());
                     bar_value.push(user_bar);
@@ -681,7 +1574,7 @@ impl MainWindow {
             if show_total_message {
                 let bar = Bar::new(arg, total_message as f64).name(format!(
                     "{} Total message",
-                    time_to_string(key, self.chart_i().chart_timing)
+                    time_to_string(key, self.chart_i().chart_timing, self.settings.date_format)
                 ));
                 let bar_value = bar_list
                     .entry("Show total data".to_owned())
@@ -692,10 +1585,10 @@ impl MainWindow {
             if show_whitelisted_message {
                 let bar = Bar::new(arg, whitelisted_message as f64).name(format!(
                     "{} Whitelisted message",
-                    time_to_string(key, self.chart_i().chart_timing)
+                    time_to_string(key, self.chart_i().chart_timing, self.settings.date_format)
                 ));
                 let bar_value = bar_list
-                    .entry("Show whitelisted data".to_owned())
+                    .entry(WHITELIST_DATA.to_owned())
                     .or_insert(Vec::new());
                 bar_value.push(bar);
             }
@@ -716,16 +1609,231 @@ impl MainWindow {
         self.display_chart(ui, show_total_message, show_whitelisted_message, bar_list);
     }
 
+    fn display_avg_words_chart(&mut self, ui: &mut Ui) {
+        let mut bars = Vec::new();
+        let mut point_dates = HashMap::new();
+
+        let chart = self.chart_i();
+        let (to_iter, words) = chart.timing_data(chart.chart_timing);
+
+        for (index, (key, user)) in to_iter.iter().enumerate() {
+            let key_date = key.date();
+
+            let within_range = chart.date_nav.handler_i().within_range(key_date);
+            let before_to_range = chart.date_nav.handler_i().before_to_range(key_date);
+
+            if !within_range && before_to_range {
+                continue;
+            }
+            if !within_range {
+                break;
+            }
+
+            let total_message: u64 = user
+                .iter()
+                .filter(|(user_name, _)| !chart.is_hidden(user_name))
+                .map(|(_, count)| count)
+                .sum();
+            let total_word: u64 = words.get(key).map_or(0, |users| {
+                users
+                    .iter()
+                    .filter(|(user_name, _)| !chart.is_hidden(user_name))
+                    .map(|(_, count)| count)
+                    .sum()
+            });
+
+            let average = if total_message == 0 {
+                0.0
+            } else {
+                total_word as f64 / total_message as f64
+            };
+
+            let bar = Bar::new(index as f64, average).name(format!(
+                "{} Average words",
+                time_to_string(key, chart.chart_timing, self.settings.date_format)
+            ));
+            bars.push(bar);
+            point_dates.insert(index as i64, (*key, total_message, total_word));
+        }
+
+        let bar_list = BTreeMap::from([("Show total data".to_string(), bars)]);
+        let show_total = self.chart_i().added_to_chart.contains("Show total data");
+
+        self.chart().labels = point_dates;
+        self.display_chart(ui, show_total, false, bar_list);
+    }
+
+    /// Shows the total messages of every counted chat as a separate line. The timing and the
+    /// date range are taken from the selected chat
+    fn display_chat_comparison_chart(&mut self, ui: &mut Ui) {
+        let timing = self.chart_i().chart_timing;
+
+        // Every chat is grouped by the custom interval of the selected chat to line them up
+        if timing == ChartTiming::Custom {
+            let interval = self.chart_i().custom_interval;
+            for chart in self.chart_all() {
+                chart.set_custom_interval(interval);
+                chart.prepare_custom_data();
+            }
+        }
+
+        let chat_names = self.counter.get_chat_list();
+        let chat_totals: Vec<BTreeMap<NaiveDateTime, u64>> = self
+            .chart_all()
+            .map(|chart| chart.total_messages(timing))
+            .collect();
+
+        let handler = self.chart_i().date_nav.handler_i();
+
+        // Chats can have messages at different times so every chat is placed on the same x values
+        let all_keys: BTreeSet<NaiveDateTime> = chat_totals
+            .iter()
+            .flat_map(BTreeMap::keys)
+            .filter(|key| handler.within_range(key.date()))
+            .copied()
+            .collect();
+        let key_index: HashMap<NaiveDateTime, usize> = all_keys
+            .iter()
+            .enumerate()
+            .map(|(index, key)| (*key, index))
+            .collect();
+
+        let mut lines = Vec::new();
+        for (index, (chat_name, totals)) in chat_names.iter().zip(&chat_totals).enumerate() {
+            if totals.is_empty() {
+                continue;
+            }
+
+            let mut points = vec![[0.0, 0.0]; all_keys.len()];
+            for (position, point) in points.iter_mut().enumerate() {
+                point[0] = position as f64;
+            }
+            for (key, total) in totals {
+                if let Some(position) = key_index.get(key) {
+                    points[*position][1] = *total as f64;
+                }
+            }
+
+            lines.push(Line::new(points).color(series_color(index)).name(chat_name));
+        }
+
+        let labels: HashMap<i64, NaiveDateTime> = all_keys
+            .into_iter()
+            .enumerate()
+            .map(|(index, key)| (index as i64, key))
+            .collect();
+
+        let date_format = self.settings.date_format;
+        let label_fmt = move |name: &str, val: &PlotPoint| {
+            let x_val = val.x.round() as i64;
+            if let Some(date) = labels.get(&x_val) {
+                format!(
+                    "{name}\n{}\nTotal Message = {:.0}",
+                    time_to_string(date, timing, date_format),
+                    val.y
+                )
+            } else {
+                format!("X = {:.0}\nY = {:.0}", val.x, val.y)
+            }
+        };
+
+        Plot::new("Comparison Plot")
+            .auto_bounds([true; 2].into())
+            .y_axis_formatter(|mark, _range| format_compact_number(mark.value))
+            .clamp_grid(true)
+            .legend(Legend::default())
+            .label_formatter(label_fmt)
+            .show(ui, |plot_ui| {
+                for line in lines {
+                    plot_ui.line(line);
+                }
+            });
+    }
+
+    /// Shows the Lorenz curve of the message count of the users in the selected date range along
+    /// with the Gini coefficient
+    fn display_concentration_chart(&mut self, ui: &mut Ui) {
+        let mut user_totals = self.chart_i().user_message_totals();
+        user_totals.sort_unstable();
+
+        let user_count = user_totals.len();
+        let total_message: u64 = user_totals.iter().sum();
+
+        let mut points = vec![[0.0, 0.0]];
+        let mut cumulative = 0;
+        for (index, count) in user_totals.iter().enumerate() {
+            cumulative += count;
+            points.push([
+                (index + 1) as f64 / user_count as f64 * 100.0,
+                cumulative as f64 / total_message as f64 * 100.0,
+            ]);
+        }
+
+        // Twice the area between the line of equality and the curve
+        let gini = if total_message == 0 {
+            0.0
+        } else {
+            let area: f64 = points
+                .windows(2)
+                .map(|pair| (pair[1][0] - pair[0][0]) * (pair[1][1] + pair[0][1]) / 2.0)
+                .sum();
+            1.0 - area / 5000.0
+        };
+
+        ui.horizontal(|ui| {
+            ui.label(format!("Users: {user_count}"));
+            ui.separator();
+            ui.label(format!("Gini Coefficient: {gini:.3}"))
+                .on_hover_text("0 means every user sent the same amount of messages, values closer to 1 mean a few users sent most of the messages");
+        });
+
+        let label_fmt = |name: &str, val: &PlotPoint| {
+            if name.is_empty() {
+                format!("X = {:.1}%\nY = {:.1}%", val.x, val.y)
+            } else {
+                format!(
+                    "{name}\n{:.1}% of the users sent {:.1}% of the messages",
+                    val.x, val.y
+                )
+            }
+        };
+
+        Plot::new("Concentration Plot")
+            .auto_bounds([true; 2].into())
+            .x_axis_formatter(|mark, _range| format!("{:.0}%", mark.value))
+            .y_axis_formatter(|mark, _range| format!("{:.0}%", mark.value))
+            .clamp_grid(true)
+            .legend(Legend::default())
+            .label_formatter(label_fmt)
+            .show(ui, |plot_ui| {
+                if total_message == 0 {
+                    return;
+                }
+                plot_ui.line(
+                    Line::new(vec![[0.0, 0.0], [100.0, 100.0]])
+                        .color(Color32::GRAY)
+                        .style(LineStyle::dashed_loose())
+                        .name("Line of Equality"),
+                );
+                plot_ui.line(
+                    Line::new(points)
+                        .color(series_color(0))
+                        .name("Message Share"),
+                );
+            });
+    }
+
     fn display_active_user_chart(&mut self, ui: &mut Ui) {
         let mut bar_list = BTreeMap::new();
         let mut point_dates = HashMap::new();
 
-        let to_iter = match self.chart().chart_timing {
-            ChartTiming::Hourly => self.chart_i().hourly_message.iter().enumerate(),
-            ChartTiming::Daily => self.chart_i().daily_message.iter().enumerate(),
-            ChartTiming::Weekly => self.chart_i().weekly_message.iter().enumerate(),
-            ChartTiming::Monthly => self.chart_i().monthly_message.iter().enumerate(),
-        };
+        // In cumulative mode every user is only counted the first time they are seen
+        let cumulative = self.chart_i().chart_type == ChartType::CumulativeUser;
+        let mut seen_users = HashSet::new();
+        let mut seen_whitelisted_users = 0;
+
+        let (message_map, _) = self.chart_i().timing_data(self.chart_i().chart_timing);
+        let to_iter = message_map.iter().enumerate();
 
         let (show_total_message, show_whitelisted_message) = self
             .chart_i()
@@ -778,8 +1886,26 @@ impl MainWindow {
             let mut total_user = 0;
             let mut whitelisted_user = 0;
 
-            if show_whitelisted_message {
+            if cumulative {
                 for user_name in user.keys() {
+                    if self.chart_i().is_hidden(user_name) || !seen_users.insert(user_name) {
+                        continue;
+                    }
+                    let is_whitelisted = self
+                        .whitelist
+                        .is_user_whitelisted(*self.chart_i().user_ids.get(user_name).unwrap());
+
+                    if is_whitelisted {
+                        seen_whitelisted_users += 1;
+                    }
+                }
+                total_user = seen_users.len();
+                whitelisted_user = seen_whitelisted_users;
+            } else if show_whitelisted_message {
+                for user_name in user.keys() {
+                    if self.chart_i().is_hidden(user_name) {
+                        continue;
+                    }
                     let is_whitelisted = self
                         .whitelist
                         .is_user_whitelisted(*self.chart_i().user_ids.get(user_name).unwrap());
@@ -790,13 +1916,16 @@ impl MainWindow {
                     total_user += 1;
                 }
             } else if show_total_message {
-                total_user += user.len();
+                total_user += user
+                    .keys()
+                    .filter(|user_name| !self.chart_i().is_hidden(user_name))
+                    .count();
             }
 
             if show_total_message {
                 let bar = Bar::new(arg, total_user as f64).name(format!(
                     "{} Total user",
-                    time_to_string(key, self.chart_i().chart_timing)
+                    time_to_string(key, self.chart_i().chart_timing, self.settings.date_format)
                 ));
                 let bar_value = bar_list
                     .entry("Show total data".to_owned())
@@ -807,10 +1936,10 @@ impl MainWindow {
             if show_whitelisted_message {
                 let bar = Bar::new(arg, f64::from(whitelisted_user)).name(format!(
                     "{} Whitelisted user",
-                    time_to_string(key, self.chart_i().chart_timing)
+                    time_to_string(key, self.chart_i().chart_timing, self.settings.date_format)
                 ));
                 let bar_value = bar_list
-                    .entry("Show whitelisted data".to_owned())
+                    .entry(WHITELIST_DATA.to_owned())
                     .or_insert(Vec::new());
                 bar_value.push(bar);
             }
@@ -835,17 +1964,18 @@ impl MainWindow {
         self.display_chart(ui, show_total_message, show_whitelisted_message, bar_list);
     }
 
-    fn display_weekday_message_chart(&mut self, ui: &mut Ui) {
+    fn display_period_message_chart(&mut self, ui: &mut Ui) {
         let mut bar_list = BTreeMap::new();
         let mut point_dates = HashMap::new();
 
-        let to_iter = self.chart_i().weekday_message.iter().enumerate();
+        let (period_data, period_name) = self.chart_i().period_data();
+        let to_iter = period_data.iter().enumerate();
 
         let (show_total_message, show_whitelisted_message) = self
             .chart_i()
             .message_whitelist_added(self.whitelist.row_len());
 
-        // Key = week day num
+        // Key = week day or hour num
         // user = All users that sent messages to this common time + the amount of message
         for (index, (key, user)) in to_iter {
             let arg = index as f64;
@@ -853,6 +1983,10 @@ impl MainWindow {
             let mut whitelisted_message = 0;
 
             for (user_name, num) in user {
+                if self.chart_i().is_hidden(user_name) {
+                    continue;
+                }
+
                 if show_whitelisted_message {
                     let is_whitelisted = self
                         .whitelist
@@ -870,7 +2004,7 @@ impl MainWindow {
 
             if show_total_message {
                 let bar = Bar::new(arg, total_message as f64)
-                    .name(format!("{} Total message", weekday_num_to_string(*key)));
+                    .name(format!("{} Total message", period_name(*key)));
                 let bar_value = bar_list
                     .entry("Show total data".to_owned())
                     .or_insert(Vec::new());
@@ -878,12 +2012,10 @@ impl MainWindow {
             }
 
             if show_whitelisted_message {
-                let bar = Bar::new(arg, whitelisted_message as f64).name(format!(
-                    "{} Whitelisted message ",
-                    weekday_num_to_string(*key)
-                ));
+                let bar = Bar::new(arg, whitelisted_message as f64)
+                    .name(format!("{} Whitelisted message ", period_name(*key)));
                 let bar_value = bar_list
-                    .entry("Show whitelisted data".to_owned())
+                    .entry(WHITELIST_DATA.to_owned())
                     .or_insert(Vec::new());
                 bar_value.push(bar);
             }
@@ -897,17 +2029,18 @@ impl MainWindow {
         self.display_chart(ui, show_total_message, show_whitelisted_message, bar_list);
     }
 
-    fn display_weekday_active_user_chart(&mut self, ui: &mut Ui) {
+    fn display_period_active_user_chart(&mut self, ui: &mut Ui) {
         let mut bar_list = BTreeMap::new();
         let mut point_dates = HashMap::new();
 
-        let to_iter = self.chart_i().weekday_message.iter().enumerate();
+        let (period_data, period_name) = self.chart_i().period_data();
+        let to_iter = period_data.iter().enumerate();
 
         let (show_total_message, show_whitelisted_message) = self
             .chart_i()
             .message_whitelist_added(self.whitelist.row_len());
 
-        // Key = week day num
+        // Key = week day or hour num
         // user = All users that sent messages to this common time + the amount of message
         for (index, (key, user)) in to_iter {
             let arg = index as f64;
@@ -916,6 +2049,9 @@ impl MainWindow {
 
             if show_whitelisted_message {
                 for user_name in user.keys() {
+                    if self.chart_i().is_hidden(user_name) {
+                        continue;
+                    }
                     let is_whitelisted = self
                         .whitelist
                         .is_user_whitelisted(*self.chart_i().user_ids.get(user_name).unwrap());
@@ -926,12 +2062,15 @@ impl MainWindow {
                     total_user += 1;
                 }
             } else if show_total_message {
-                total_user += user.len();
+                total_user += user
+                    .keys()
+                    .filter(|user_name| !self.chart_i().is_hidden(user_name))
+                    .count();
             }
 
             if show_total_message {
                 let bar = Bar::new(arg, total_user as f64)
-                    .name(format!("{} Total user", weekday_num_to_string(*key)));
+                    .name(format!("{} Total user", period_name(*key)));
                 let bar_value = bar_list
                     .entry("Show total data".to_owned())
                     .or_insert(Vec::new());
@@ -940,9 +2079,9 @@ impl MainWindow {
 
             if show_whitelisted_message {
                 let bar = Bar::new(arg, f64::from(whitelisted_user))
-                    .name(format!("{} Whitelisted user", weekday_num_to_string(*key)));
+                    .name(format!("{} Whitelisted user", period_name(*key)));
                 let bar_value = bar_list
-                    .entry("Show whitelisted data".to_owned())
+                    .entry(WHITELIST_DATA.to_owned())
                     .or_insert(Vec::new());
                 bar_value.push(bar);
             }
@@ -970,84 +2109,136 @@ impl MainWindow {
         let mut all_charts = Vec::new();
 
         let total_data_name = match self.chart().chart_type {
-            ChartType::Message | ChartType::MessageWeekDay => "Total Message",
-            ChartType::ActiveUser | ChartType::ActiveUserWeekDay => "Total User",
+            ChartType::Message
+            | ChartType::MessageWeekDay
+            | ChartType::MessageHourOfDay
+            | ChartType::ChatComparison
+            | ChartType::Concentration
+            | ChartType::WordCloud => "Total Message",
+            ChartType::ActiveUser
+            | ChartType::ActiveUserWeekDay
+            | ChartType::ActiveUserHourOfDay => "Total User",
+            ChartType::CumulativeUser => "Total Unique User",
+            ChartType::AvgWordsOverTime => "Average Words",
         };
 
         let whitelist_data_name = match self.chart().chart_type {
-            ChartType::Message | ChartType::MessageWeekDay => "Whitelisted Message",
-            ChartType::ActiveUser | ChartType::ActiveUserWeekDay => "Whitelisted User",
+            ChartType::Message
+            | ChartType::MessageWeekDay
+            | ChartType::MessageHourOfDay
+            | ChartType::ChatComparison
+            | ChartType::Concentration
+            | ChartType::WordCloud => "Whitelisted Message",
+            ChartType::ActiveUser
+            | ChartType::ActiveUserWeekDay
+            | ChartType::ActiveUserHourOfDay => "Whitelisted User",
+            ChartType::CumulativeUser => "Whitelisted Unique User",
+            ChartType::AvgWordsOverTime => "Average Words",
         };
 
         // Whitelist message should be above the total message
         // In case the date picker is used the bar list may not contain the following bar names
         // even if they are already in the list
+        let series_name = |key: &str| match key {
+            "Show total data" => total_data_name.to_string(),
+            WHITELIST_DATA => whitelist_data_name.to_string(),
+            _ => key.to_string(),
+        };
+
+        let mut all_series = Vec::new();
+        if show_total_message {
+            if let Some(total_message_bars) = bar_list.remove("Show total data") {
+                all_series.push(("Show total data".to_string(), total_message_bars));
+            }
+        }
         if show_whitelisted_message {
-            if let Some(whitelist_bar) = bar_list.remove("Show whitelisted data") {
-                let mut whitelist_chart = BarChart::new(whitelist_bar)
-                    .width(1.0)
-                    .name(whitelist_data_name);
+            if let Some(whitelist_bar) = bar_list.remove(WHITELIST_DATA) {
+                all_series.push((WHITELIST_DATA.to_string(), whitelist_bar));
+            }
+        }
 
-                if show_total_message {
-                    if let Some(total_message_bars) = bar_list.remove("Show total data") {
-                        let total_message_chart = BarChart::new(total_message_bars)
-                            .width(1.0)
-                            .name(total_data_name);
+        // User data only shows up on Message chart
+        // The target is the bottom chart is total message => whitelist => the rest of the users
+        if self.chart().chart_type == ChartType::Message {
+            all_series.extend(bar_list);
+        }
 
-                        whitelist_chart = whitelist_chart.stack_on(&[&total_message_chart]);
-                        all_charts.push(total_message_chart);
-                    };
+        // Colors are set here so the same color can be shown on the legend
+        let all_series: Vec<(String, Vec<Bar>, Color32)> = all_series
+            .into_iter()
+            .enumerate()
+            .map(|(index, (key, bars))| (key, bars, series_color(index)))
+            .collect();
+
+        self.show_chart_legend(ui, &all_series, series_name);
+
+        let grouped =
+            self.chart_i().grouped_bars && self.chart_i().chart_type == ChartType::Message;
+
+        if grouped {
+            // Split the width of each x value among all the data and shift every bar to its slot
+            let bar_width = 1.0 / all_series.len() as f64;
+            for (index, (key, mut bars, color)) in all_series.into_iter().enumerate() {
+                let offset = bar_width * (index as f64 + 0.5) - 0.5;
+                for bar in &mut bars {
+                    bar.argument += offset;
                 }
-                all_charts.push(whitelist_chart);
+                all_charts.push(
+                    BarChart::new(bars)
+                        .width(bar_width)
+                        .color(color)
+                        .name(series_name(&key)),
+                );
             }
-        } else if show_total_message {
-            if let Some(total_message_bars) = bar_list.remove("Show total data") {
-                let total_message_chart = BarChart::new(total_message_bars)
-                    .width(1.0)
-                    .name(total_data_name);
-                all_charts.push(total_message_chart);
-            };
-        };
-
-        // User data stacking only happens on Message chart
-        if self.chart().chart_type == ChartType::Message {
-            // Only triggered when Something other than total and whitelisted message is added to
-            // the chart
+        } else {
             // All charts must be stacked by all the previous charts
             // Chart 3 will be stacked by chart 1 and 2
-            // The target is the bottom chart is total message => whitelist => the rest of the users
-            if !bar_list.is_empty() {
-                for (name, bar) in bar_list {
-                    let current_chart = BarChart::new(bar).width(1.0).name(name);
-
-                    if all_charts.is_empty() {
-                        all_charts.push(current_chart);
-                    } else {
-                        let current_chart =
-                            current_chart.stack_on(&all_charts.iter().collect::<Vec<&BarChart>>());
-                        all_charts.push(current_chart);
-                    }
+            for (key, bars, color) in all_series {
+                let current_chart = BarChart::new(bars)
+                    .width(1.0)
+                    .color(color)
+                    .name(series_name(&key));
+
+                if all_charts.is_empty() {
+                    all_charts.push(current_chart);
+                } else {
+                    let current_chart =
+                        current_chart.stack_on(&all_charts.iter().collect::<Vec<&BarChart>>());
+                    all_charts.push(current_chart);
                 }
             }
         }
         let timing = self.chart().chart_timing;
+        let custom_interval = self.chart_i().custom_interval;
         let chart_type = self.chart().chart_type;
-        let is_weekday =
-            chart_type == ChartType::MessageWeekDay || chart_type == ChartType::ActiveUserWeekDay;
+        // Weekday, hour of day and average word charts do not use the pre-saved labels
+        let uses_saved = !chart_type.is_period() && chart_type != ChartType::AvgWordsOverTime;
 
-        let labels = if timing == ChartTiming::Hourly && !is_weekday {
+        let labels = if timing == ChartTiming::Hourly && uses_saved {
             self.chart().hourly_labels.clone()
-        } else if timing == ChartTiming::Daily && !is_weekday {
+        } else if timing == ChartTiming::Daily && uses_saved {
             self.chart().daily_labels.clone()
         } else {
             self.chart().labels.clone()
         };
 
+        let date_format = self.settings.date_format;
         let label_fmt = move |_s: &str, val: &PlotPoint| {
             let x_val = val.x.round() as i64;
             if let Some((date, total, whitelist)) = labels.get(&x_val) {
+                if chart_type == ChartType::AvgWordsOverTime {
+                    return format!(
+                        "{}\nY = {:.2}\nTotal Message = {}\nTotal Word = {}",
+                        time_to_string(date, timing, date_format),
+                        val.y,
+                        total,
+                        whitelist
+                    );
+                }
+
                 let label_type = if chart_type == ChartType::Message
                     || chart_type == ChartType::MessageWeekDay
+                    || chart_type == ChartType::MessageHourOfDay
                 {
                     "Message"
                 } else {
@@ -1057,24 +2248,50 @@ impl MainWindow {
                 let date_label;
 
                 match chart_type {
-                    ChartType::Message | ChartType::ActiveUser => {
+                    ChartType::Message
+                    | ChartType::ActiveUser
+                    | ChartType::CumulativeUser
+                    | ChartType::AvgWordsOverTime
+                    | ChartType::ChatComparison
+                    | ChartType::Concentration
+                    | ChartType::WordCloud => {
                         match timing {
                             ChartTiming::Hourly | ChartTiming::Daily => {
-                                date_label = date.to_string();
+                                date_label = date_format.format_time(date);
                             }
                             ChartTiming::Weekly => {
                                 let other_date = date.checked_add_days(Days::new(7)).unwrap();
-                                date_label = format!("{date} - {other_date}");
+                                date_label = format!(
+                                    "{} - {}",
+                                    date_format.format_time(date),
+                                    date_format.format_time(&other_date)
+                                );
                             }
                             ChartTiming::Monthly => {
                                 let other_date = date.checked_add_months(Months::new(1)).unwrap();
-                                date_label = format!("{date} - {other_date}");
+                                date_label = format!(
+                                    "{} - {}",
+                                    date_format.format_time(date),
+                                    date_format.format_time(&other_date)
+                                );
+                            }
+                            ChartTiming::Custom => {
+                                let other_date =
+                                    *date + Duration::minutes(i64::from(custom_interval));
+                                date_label = format!(
+                                    "{} - {}",
+                                    date_format.format_time(date),
+                                    date_format.format_time(&other_date)
+                                );
                             }
                         };
                     }
                     ChartType::MessageWeekDay | ChartType::ActiveUserWeekDay => {
                         date_label = weekday_num_to_string(x_val as u8);
                     }
+                    ChartType::MessageHourOfDay | ChartType::ActiveUserHourOfDay => {
+                        date_label = hour_num_to_string(x_val as u8);
+                    }
                 }
                 format!(
                     "{}\nY = {:.0}\nTotal {label_type} = {}\nWhitelisted {label_type} = {}",
@@ -1085,15 +2302,21 @@ impl MainWindow {
             }
         };
 
-        Plot::new("Plot")
-            .legend(Legend::default().background_alpha(0.0))
+        let data_added = std::mem::take(&mut self.chart().data_added);
+        let mut plot = Plot::new("Plot")
             .auto_bounds([true; 2].into())
+            .y_axis_formatter(|mark, _range| format_compact_number(mark.value))
             .clamp_grid(true)
-            .label_formatter(label_fmt)
-            .show(ui, |plot_ui| {
-                for chart in all_charts {
-                    plot_ui.bar_chart(chart);
-                }
-            });
+            .label_formatter(label_fmt);
+
+        if self.settings.auto_fit_chart && self.is_processing && data_added {
+            plot = plot.reset();
+        }
+
+        plot.show(ui, |plot_ui| {
+            for chart in all_charts {
+                plot_ui.bar_chart(chart);
+            }
+        });
     }
 }
@@ -1,16 +1,43 @@
 use eframe::egui::{
-    vec2, Align, Button, ComboBox, Grid, Label, Layout, ProgressBar, TextEdit, Ui, ViewportCommand,
+    vec2, Align, Button, ComboBox, Grid, Id, Key, Label, Layout, Modal, Modifiers, ProgressBar,
+    ScrollArea, TextEdit, Ui, ViewportCommand,
 };
 use log::info;
-use std::collections::HashMap;
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::env::current_dir;
 use std::sync::atomic::Ordering;
+use std::time::{Duration, Instant};
 
-use crate::tg_handler::ProcessStart;
-use crate::ui_components::processor::{CounterCounts, ParsedChat, ProcessState};
+use crate::tg_handler::{ProcessStart, TGClient};
+use crate::ui_components::processor::{
+    CounterCounts, MessageCap, MessageFilter, ParsedChat, ProcessState, SenderFilter,
+};
+use crate::ui_components::widgets::ProgressRing;
 use crate::ui_components::MainWindow;
-use crate::utils::{chat_to_text, parse_chat_details};
+use crate::utils::{
+    chat_to_text, create_stream_file, get_checkpoints, parse_chat_details, save_settings,
+};
 
 const LIMIT_SELECTION: [&str; 5] = ["30", "40", "50", "80", "100"];
+const RELOAD_SELECTION: [&str; 6] = ["50", "100", "250", "500", "750", "1000"];
+/// Number of users shown in the leaderboard while counting
+const LEADERBOARD_SIZE: usize = 5;
+/// How often the leaderboard is recalculated while counting
+const LEADERBOARD_INTERVAL: Duration = Duration::from_secs(1);
+/// Approximate number of messages a session can count before flood wait is triggered
+const FLOOD_WAIT_THRESHOLD: u32 = 3000;
+/// Share of the flood wait threshold after which the session gauge is shown as a warning
+const FLOOD_WAIT_WARNING: f32 = 0.8;
+
+/// Message ranges resolved so far by a preview
+#[derive(Clone, Default)]
+struct RangePreview {
+    /// Chats that are yet to be resolved
+    pending: usize,
+    total_message: i64,
+    total_chats: usize,
+    failed_chats: usize,
+}
 
 #[derive(Clone)]
 pub struct CounterData {
@@ -19,16 +46,50 @@ pub struct CounterData {
     end_at: String,
     pub counts: Vec<CounterCounts>,
     bar_percentage: f32,
-    use_all_sessions: bool,
+    /// Sessions that participate in a multi session count
+    enabled_sessions: BTreeSet<String>,
     counting: bool,
     session_count: usize,
     session_percentage: HashMap<String, f32>,
     comm_limit: usize,
+    reload_threshold: usize,
     parsed_chat_list: HashMap<String, ParsedChat>,
     chat_list: Vec<String>,
     ongoing_chat: usize,
     detected_chat: String,
     retain_data: bool,
+    incremental: bool,
+    message_filter: String,
+    /// Username or user ID of the only sender whose messages are counted
+    sender_filter: String,
+    message_cap: String,
+    /// Cap shared by the sessions counting the ongoing chat
+    active_cap: Option<MessageCap>,
+    /// Chats where counting stopped at the cap in the current counting session
+    capped_chats: usize,
+    stream_export: bool,
+    count_only: bool,
+    /// Whether service messages such as joins, pins and title changes are not counted
+    skip_service: bool,
+    /// Whether stickers and GIFs are not counted
+    skip_stickers: bool,
+    count_threads: bool,
+    show_thread_modal: bool,
+    merge_first: usize,
+    merge_second: usize,
+    show_reset_modal: bool,
+    /// Chats that were exported automatically in the current counting session
+    auto_saved: usize,
+    /// The ongoing preview of the message range, if any
+    preview: Option<RangePreview>,
+    /// Users with the most messages in the ongoing chat with their message count
+    leaderboard: Vec<(String, u32)>,
+    /// When the leaderboard was last recalculated
+    leaderboard_updated: Option<Instant>,
+    /// Messages counted by each session in the current counting session
+    session_messages: BTreeMap<String, u32>,
+    /// The message range each chat was originally counted with, used for recounting
+    chat_ranges: HashMap<String, ParsedChat>,
 }
 
 impl Default for CounterData {
@@ -39,16 +100,38 @@ impl Default for CounterData {
             end_at: String::default(),
             counts: vec![CounterCounts::default()],
             bar_percentage: f32::default(),
-            use_all_sessions: true,
+            enabled_sessions: BTreeSet::new(),
             counting: bool::default(),
             session_count: usize::default(),
             session_percentage: HashMap::default(),
             comm_limit: 4,
+            reload_threshold: 1,
             parsed_chat_list: HashMap::default(),
             chat_list: Vec::default(),
             ongoing_chat: usize::default(),
             detected_chat: String::default(),
             retain_data: true,
+            incremental: false,
+            message_filter: String::default(),
+            sender_filter: String::default(),
+            message_cap: String::default(),
+            active_cap: None,
+            capped_chats: 0,
+            stream_export: false,
+            count_only: false,
+            skip_service: false,
+            skip_stickers: false,
+            count_threads: false,
+            show_thread_modal: false,
+            merge_first: 0,
+            merge_second: 1,
+            show_reset_modal: false,
+            auto_saved: 0,
+            preview: None,
+            leaderboard: Vec::new(),
+            leaderboard_updated: None,
+            session_messages: BTreeMap::new(),
+            chat_ranges: HashMap::new(),
         }
     }
 }
@@ -57,6 +140,7 @@ impl CounterData {
     pub fn reset(&mut self) {
         self.counts = vec![CounterCounts::default()];
         self.chat_list = Vec::new();
+        self.chat_ranges = HashMap::new();
         self.ongoing_chat = 0;
         self.session_percentage = HashMap::new();
     }
@@ -68,6 +152,14 @@ impl CounterData {
         self.session_percentage.entry(to_add).or_default();
     }
 
+    /// Add one counted message to the total of the session
+    pub fn add_session_message(&mut self, session: &str) {
+        *self
+            .session_messages
+            .entry(session.to_string())
+            .or_default() += 1;
+    }
+
     pub fn set_session_percentage(&mut self, key: &str, value: f32) {
         *self.session_percentage.get_mut(key).unwrap() = value;
         let mut progress_bar = 0.0;
@@ -133,6 +225,10 @@ impl CounterData {
         LIMIT_SELECTION[self.comm_limit].parse().unwrap()
     }
 
+    pub fn get_reload_threshold(&self) -> u32 {
+        RELOAD_SELECTION[self.reload_threshold].parse().unwrap()
+    }
+
     pub fn total_parsed_chats(&self) -> usize {
         self.parsed_chat_list.len()
     }
@@ -199,10 +295,90 @@ impl CounterData {
     pub fn selected_chat_name(&self, index: usize) -> String {
         self.chat_list[index].clone()
     }
+
+    /// Whether the ongoing count covered every message of the chat down to the previous checkpoint
+    /// or the first message. Capped, filtered and explicitly ended counts leave gaps that a
+    /// checkpoint would skip on the next incremental count
+    pub fn covered_full_range(&self, chat: &str) -> bool {
+        if self.active_cap.is_some()
+            || self.message_filter().is_some()
+            || self.sender_filter().is_some()
+        {
+            return false;
+        }
+
+        match self.chat_ranges.get(chat).map(ParsedChat::end_point) {
+            Some(None) => true,
+            Some(Some(end_point)) => get_checkpoints()
+                .get(chat)
+                .is_some_and(|checkpoint| end_point <= checkpoint + 1),
+            None => false,
+        }
+    }
+
+    /// Whether the chat was counted in this session and can be counted again with the same range
+    pub fn can_recount(&self, chat: &str) -> bool {
+        self.chat_ranges.contains_key(chat)
+    }
+
+    /// Mark a session as participating in multi session counting
+    pub fn enable_session(&mut self, name: String) {
+        self.enabled_sessions.insert(name);
+    }
+
+    /// Whether the chat details are being resolved only to preview the message range
+    pub fn previewing(&self) -> bool {
+        self.preview.is_some()
+    }
+
+    pub fn end_preview(&mut self) {
+        self.preview = None;
+    }
+
+    pub fn add_auto_saved(&mut self) {
+        self.auto_saved += 1;
+    }
+
+    pub fn message_filter(&self) -> Option<MessageFilter> {
+        MessageFilter::new(&self.message_filter)
+    }
+
+    pub fn sender_filter(&self) -> Option<SenderFilter> {
+        SenderFilter::new(&self.sender_filter)
+    }
+
+    pub fn skip_service(&self) -> bool {
+        self.skip_service
+    }
+
+    pub fn skip_stickers(&self) -> bool {
+        self.skip_stickers
+    }
+
+    /// The cap of the ongoing chat
+    pub fn active_cap(&self) -> Option<MessageCap> {
+        self.active_cap.clone()
+    }
+
+    /// Whether the ongoing chat stopped counting because the cap was reached
+    pub fn cap_reached(&self) -> bool {
+        self.active_cap.as_ref().is_some_and(MessageCap::exceeded)
+    }
+
+    pub fn add_capped_chat(&mut self) {
+        self.capped_chats += 1;
+    }
 }
 
 impl MainWindow {
     pub fn show_counter_ui(&mut self, ui: &mut Ui) {
+        if !self.counter.show_reset_modal
+            && !self.counter.show_thread_modal
+            && !self.show_close_modal
+        {
+            self.handle_counter_shortcuts(ui);
+        }
+
         Grid::new("Counter Grid")
             .num_columns(2)
             .spacing([5.0, 10.0])
@@ -252,6 +428,26 @@ impl MainWindow {
 
                     ui.end_row();
 
+                    ui.with_layout(Layout::right_to_left(Align::Center), |ui| {
+                        ui.label("Total Words:")
+                    });
+                    ui.with_layout(Layout::left_to_right(Align::Center), |ui| {
+                        ui.label(format!("{}", self.count().total_word));
+                    });
+
+                    ui.end_row();
+
+                    ui.with_layout(Layout::right_to_left(Align::Center), |ui| {
+                        ui.label("Total Characters:")
+                    });
+                    ui.with_layout(Layout::left_to_right(Align::Center), |ui| {
+                        let char_config = self.settings.char_count;
+                        ui.label(format!("{}", self.count().char_counts.get(char_config)))
+                            .on_hover_text("Counted with the char count mode of the User Table");
+                    });
+
+                    ui.end_row();
+
                     ui.with_layout(Layout::right_to_left(Align::Center), |ui| {
                         ui.label("Deleted Message:")
                     });
@@ -260,37 +456,163 @@ impl MainWindow {
                     });
 
                     ui.end_row();
+
+                    ui.with_layout(Layout::right_to_left(Align::Center), |ui| {
+                        ui.label("Deleted %:")
+                    });
+                    ui.with_layout(Layout::left_to_right(Align::Center), |ui| {
+                        ui.label(format!("{:.2}%", self.count().deleted_percentage()));
+                    });
+
+                    ui.end_row();
                 });
             ui.with_layout(Layout::right_to_left(Align::Center), |ui| {
                 ui.add_space(80.0);
 
                 if self.is_processing && self.counter.counting() {
-                    let cancel_button = ui.add_sized([80.0, 40.0], Button::new("Cancel"));
+                    let cancel_button = ui
+                        .add_sized([80.0, 40.0], Button::new("Cancel"))
+                        .on_hover_text("Esc");
                     if cancel_button.clicked() {
                         self.cancel_count();
                     }
                 } else if self.is_processing {
                     ui.add_enabled(false, Button::new("Start").min_size(vec2(80.0, 40.0)));
                 } else {
-                    let start_button = ui.add_sized([80.0, 40.0], Button::new("Start"));
+                    let start_button = ui
+                        .add_sized([80.0, 40.0], Button::new("Start"))
+                        .on_hover_text("Ctrl+Enter");
                     if start_button.clicked() {
                         self.start_counting();
                     }
                 };
+
+                ui.add(ProgressRing::new(
+                    self.counter.bar_percentage,
+                    40.0,
+                    Id::new("counter_progress_ring"),
+                ))
+                .on_hover_text("Counting progress of the ongoing chat");
+
+                let preview_button = ui
+                    .add_enabled(
+                        !self.is_processing,
+                        Button::new("Preview").min_size(vec2(80.0, 40.0)),
+                    )
+                    .on_hover_text(
+                        "Check how many messages the given range spans without counting",
+                    );
+                if preview_button.clicked() {
+                    self.preview_range();
+                }
+
+                let reset_button = ui
+                    .add_enabled(
+                        !self.is_processing,
+                        Button::new("Reset All").min_size(vec2(80.0, 40.0)),
+                    )
+                    .on_hover_text("Clear all counted chats, tables, charts and counts");
+                if reset_button.clicked() {
+                    self.counter.show_reset_modal = true;
+                }
+
+                let summary_button = ui
+                    .add_enabled(
+                        self.counter.total_chats() > 0,
+                        Button::new("Copy Summary").min_size(vec2(80.0, 40.0)),
+                    )
+                    .on_hover_text("Copy a text summary of the statistics of the selected chat");
+                if summary_button.clicked() {
+                    let summary = self.chat_summary(self.counter_chat_index);
+                    ui.ctx().copy_text(summary);
+                }
             });
         });
 
+        if self.counter.show_reset_modal {
+            self.show_reset_modal(ui);
+        }
+
+        if self.counter.show_thread_modal {
+            self.show_thread_modal(ui);
+        }
+
+        if self.counter.counting() {
+            self.update_leaderboard();
+        }
+
         ui.with_layout(Layout::bottom_up(Align::Min), |ui| {
             let progress_bar = ProgressBar::new(self.counter.bar_percentage)
                 .show_percentage()
                 .animate(self.counter.counting);
             ui.add(progress_bar);
+
+            if self.counter.counting() && !self.counter.session_messages.is_empty() {
+                self.show_session_gauges(ui);
+            }
+
+            if self.counter.counting() && !self.counter.leaderboard.is_empty() {
+                ui.vertical(|ui| {
+                    ui.strong("Most active so far:");
+                    for (index, (name, total_message)) in
+                        self.counter.leaderboard.iter().enumerate()
+                    {
+                        ui.label(format!("{}. {name}: {total_message}", index + 1));
+                    }
+                });
+            }
         });
 
         self.counter.detected_chat =
             chat_to_text(&self.counter.get_start_from(), &self.counter.get_end_at());
     }
 
+    /// Ctrl+Enter starts counting and Esc cancels it, same as the Start and Cancel buttons
+    fn handle_counter_shortcuts(&mut self, ui: &mut Ui) {
+        // Consumed before the text boxes are drawn so the key press never reaches them
+        let start_pressed = ui
+            .ctx()
+            .input_mut(|i| i.consume_key(Modifiers::COMMAND, Key::Enter));
+        if start_pressed && !self.is_processing {
+            self.start_counting();
+            return;
+        }
+
+        let cancel_allowed = self.is_processing && self.counter.counting();
+        if cancel_allowed
+            && ui
+                .ctx()
+                .input_mut(|i| i.consume_key(Modifiers::NONE, Key::Escape))
+        {
+            self.cancel_count();
+        }
+    }
+
+    /// A button to view and edit the note of the chat. The notes are saved on every edit
+    pub fn show_chat_note(&mut self, ui: &mut Ui, chat_name: &str) {
+        let mut note = self.settings.chat_note(chat_name);
+        let hover_text = if note.is_empty() {
+            String::from("Add a note about this chat")
+        } else {
+            note.clone()
+        };
+
+        ui.menu_button("📝", |ui| {
+            ui.label(format!("Note for {chat_name}"));
+            let response = ui.add(
+                TextEdit::multiline(&mut note)
+                    .hint_text("What is this chat about?")
+                    .desired_rows(3),
+            );
+            if response.changed() {
+                self.settings.set_chat_note(chat_name, note);
+                save_settings(&self.settings);
+            }
+        })
+        .response
+        .on_hover_text(hover_text);
+    }
+
     fn show_grid_data(&mut self, ui: &mut Ui) {
         ui.with_layout(Layout::right_to_left(Align::Center), |ui| {
             ui.add(Label::new("Selected Chat:"));
@@ -312,9 +634,52 @@ impl MainWindow {
                 len,
                 |i| &values[i],
             );
+            if len > 0 {
+                let chat_name = self.counter.selected_chat_name(self.counter_chat_index);
+                self.show_chat_note(ui, &chat_name);
+            }
             ui.separator();
             ui.checkbox(&mut self.counter.retain_data, "Retain previous data")
                 .on_hover_text("Whether to retain all previous data on a new counting session");
+            ui.checkbox(&mut self.counter.incremental, "Incremental")
+                .on_hover_text(
+                    "Only count the messages newer than the last counted message of a chat
+
+Applies to chats without an end point that were fully counted before. Counts with a message cap, a message filter, a sender filter or an end point do not mark the chat as counted. New data is added to the existing data if it was retained",
+                );
+            ui.checkbox(&mut self.counter.stream_export, "Stream to JSONL")
+                .on_hover_text(
+                    "Whether to write a summary of every counted message to a JSONL file while counting
+
+Creates one file per chat in the current directory without keeping the data in memory",
+                );
+            ui.checkbox(&mut self.counter.count_only, "Count only")
+                .on_hover_text(
+                    "Count only (skip per-user stats)
+
+Only the message counts are tracked. User Table and Charts will be disabled for the counted chats",
+                );
+            ui.checkbox(&mut self.counter.skip_service, "Skip service messages")
+                .on_hover_text(
+                    "Whether to skip service messages such as member joins, pinned messages and title changes",
+                );
+            ui.checkbox(&mut self.counter.skip_stickers, "Skip stickers and GIFs")
+                .on_hover_text(
+                    "Whether to skip stickers and GIFs
+
+They have no text and lower the average words and chars when counted. Otherwise they are counted as messages and tracked in the Stickers column",
+                );
+            if ui
+                .checkbox(&mut self.settings.auto_save, "Auto-save on finish")
+                .on_hover_text(
+                    "Whether to export the User Table of each chat to a CSV file once its counting finishes
+
+Files are created in the current directory. Cancelled counts are not saved",
+                )
+                .changed()
+            {
+                save_settings(&self.settings);
+            }
         });
         ui.end_row();
 
@@ -337,15 +702,39 @@ impl MainWindow {
                 self.tg_clients.len(),
                 |i| &values[i],
             );
-            ui.checkbox(&mut self.counter.use_all_sessions, "Use all sessions")
+            let session_names = self.get_session_names();
+            let total_enabled = session_names
+                .iter()
+                .filter(|name| self.counter.enabled_sessions.contains(*name))
+                .count();
+
+            ui.add_enabled_ui(!self.is_processing, |ui| {
+                ui.menu_button(
+                    format!("Sessions ({total_enabled}/{})", session_names.len()),
+                    |ui| {
+                        for name in session_names {
+                            let mut enabled = self.counter.enabled_sessions.contains(&name);
+                            if ui.checkbox(&mut enabled, &name).changed() {
+                                if enabled {
+                                    self.counter.enabled_sessions.insert(name);
+                                } else {
+                                    self.counter.enabled_sessions.remove(&name);
+                                }
+                            }
+                        }
+                    },
+                )
+                .response
                 .on_hover_text(
-                    "Whether to use all the available sessions for counting
-            
-Automatically divides the tasks among sessions, dramatically increasing speed.
+                    "Which sessions to use for counting
+
+If 2 or more sessions are selected, the tasks are automatically divided among them, dramatically increasing speed.
+Otherwise the session selected on the left is used.
 
 How to get more sessions?
 Login to one or more accounts multiple times with different session names!",
                 );
+            });
 
             ui.separator();
 
@@ -367,6 +756,24 @@ visible in the UI.
 
 Info: Each session can count about 3000 messages before flood wait is triggered.",
                 );
+
+            ui.separator();
+
+            ui.label("Reload:");
+
+            ComboBox::from_id_salt("Reload Box")
+                .width(60.0)
+                .show_index(
+                    ui,
+                    &mut self.counter.reload_threshold,
+                    RELOAD_SELECTION.len(),
+                    |i| RELOAD_SELECTION[i],
+                )
+                .on_hover_text(
+                    "How many messages to count before the User Table is refreshed while counting? Default: 100
+
+Higher values make counting smoother at the cost of less frequent table updates.",
+                );
         });
         ui.end_row();
 
@@ -399,6 +806,12 @@ Multiple input format is supported:
 2. t.me/chat_name/1234 t.me/chat_name_2/1234
 3. @chat_name/1234 @chat_name_2/1234
 4. chat_name/1234 chat_name_2/1234
+5. https://t.me/c/1234567890/1234 for private channels the session has joined
+6. chat_name 1234 1000 to give the starting and the ending message number directly
+7. me/1234 for the Saved Messages of the session
+8. 1234567890/1234 for a user ID the session has a DM with, 1234567890/ to start from the latest message
+
+A number on its own is always a message number, chat IDs need the slash.
 
 If message number is not specified, starts from the latest message.
 Starting message number will always be bigger than the ending message.
@@ -445,6 +858,10 @@ Multiple input format is supported:
 2. t.me/chat_name/1234
 3. @chat_name/1234
 4. chat_name/1234
+5. https://t.me/c/1234567890/1234
+6. chat_name 1234
+7. me/1234
+8. 1234567890/1234
 
 If message number is not specified or is empty, counts all messages.
 Ending message number will always be smaller than the starting message.
@@ -460,6 +877,320 @@ To count all messages in a chat, paste the very first message link or keep it em
             }
         });
         ui.end_row();
+
+        ui.with_layout(Layout::right_to_left(Align::Center), |ui| {
+            ui.add(Label::new("Message Filter:"));
+        });
+
+        ui.with_layout(Layout::right_to_left(Align::Center), |ui| {
+            if !MessageFilter::is_valid_regex(&self.counter.message_filter) {
+                ui.label("⚠")
+                    .on_hover_text("Invalid regex. Messages will be matched by plain text instead");
+            }
+
+            ui.add_sized(
+                ui.available_size(),
+                TextEdit::singleline(&mut self.counter.message_filter)
+                    .hint_text("(Optional) keyword or regex"),
+            )
+            .on_hover_text(
+                "Only count messages that contain a match of this keyword or regex.
+Matching is case insensitive. Keep it empty to count all messages.
+
+If the regex is invalid, messages containing the text will be counted.",
+            );
+        });
+        ui.end_row();
+
+        ui.with_layout(Layout::right_to_left(Align::Center), |ui| {
+            ui.add(Label::new("Sender Filter:"));
+        });
+
+        ui.with_layout(Layout::right_to_left(Align::Center), |ui| {
+            ui.add_sized(
+                ui.available_size(),
+                TextEdit::singleline(&mut self.counter.sender_filter)
+                    .hint_text("(Optional) username or user ID"),
+            )
+            .on_hover_text(
+                "Only count messages sent by this user. Keep it empty to count messages of all users.",
+            );
+        });
+        ui.end_row();
+
+        ui.with_layout(Layout::right_to_left(Align::Center), |ui| {
+            ui.add(Label::new("Message Cap:"));
+        });
+
+        ui.with_layout(Layout::right_to_left(Align::Center), |ui| {
+            if !MessageCap::is_valid(&self.counter.message_cap) {
+                ui.label("⚠")
+                    .on_hover_text("Invalid number. Messages will be counted without a cap");
+            }
+
+            ui.add_sized(
+                ui.available_size(),
+                TextEdit::singleline(&mut self.counter.message_cap)
+                    .hint_text("(Optional) stop after N messages"),
+            )
+            .on_hover_text(
+                "Stop counting a chat after this many messages are counted.
+Keep it empty to count every message between the starting and the ending point.
+
+When counting with multiple sessions, the cap applies to the total of all sessions.",
+            );
+        });
+        ui.end_row();
+
+        ui.with_layout(Layout::right_to_left(Align::Center), |ui| {
+            ui.add(Label::new("Merge Chats:"));
+        });
+
+        ui.with_layout(Layout::left_to_right(Align::Center), |ui| {
+            let names = self.counter.get_chat_list();
+            let len = names.len();
+
+            if self.counter.merge_first >= len {
+                self.counter.merge_first = 0;
+            }
+            if self.counter.merge_second >= len {
+                self.counter.merge_second = 0;
+            }
+
+            let values = if names.is_empty() {
+                vec!["No chat available".to_string()]
+            } else {
+                names
+            };
+
+            ComboBox::from_id_salt("Merge First Box").show_index(
+                ui,
+                &mut self.counter.merge_first,
+                len,
+                |i| &values[i],
+            );
+            ComboBox::from_id_salt("Merge Second Box").show_index(
+                ui,
+                &mut self.counter.merge_second,
+                len,
+                |i| &values[i],
+            );
+
+            let merge_enabled = !self.is_processing
+                && len > 1
+                && self.counter.merge_first != self.counter.merge_second;
+            if ui
+                .add_enabled(merge_enabled, Button::new("Merge"))
+                .on_hover_text("Combine the data of the two selected chats into a new chat entry")
+                .clicked()
+            {
+                self.merge_chats(self.counter.merge_first, self.counter.merge_second);
+            }
+        });
+        ui.end_row();
+
+        ui.with_layout(Layout::right_to_left(Align::Center), |ui| {
+            ui.add(Label::new("Threads:"));
+        });
+
+        ui.with_layout(Layout::left_to_right(Align::Center), |ui| {
+            ui.checkbox(&mut self.counter.count_threads, "Count per thread")
+                .on_hover_text(
+                    "Group the messages by the reply thread or forum topic they belong to",
+                );
+
+            let has_threads = self.counter.total_chats() > 0 && !self.count().threads.is_empty();
+            if ui
+                .add_enabled(has_threads, Button::new("View Threads"))
+                .on_hover_text("Show the message count of each thread of the selected chat")
+                .clicked()
+            {
+                self.counter.show_thread_modal = true;
+            }
+        });
+        ui.end_row();
+    }
+
+    /// Confirmation modal before wiping all counted data
+    fn show_reset_modal(&mut self, ui: &mut Ui) {
+        let modal = Modal::new(Id::new("reset_modal")).show(ui.ctx(), |ui| {
+            ui.set_width(250.0);
+            ui.vertical_centered(|ui| {
+                ui.heading("Reset all data?");
+            });
+            ui.add_space(5.0);
+            ui.label("All counted chats, tables, charts and counts will be removed.");
+            ui.add_space(5.0);
+
+            ui.horizontal(|ui| {
+                let button_width = ui.available_width() / 2.0;
+
+                if ui
+                    .add_sized([button_width - 5.0, 15.0], Button::new("Reset"))
+                    .clicked()
+                {
+                    self.counter.show_reset_modal = false;
+                    self.reset_all();
+                }
+
+                if ui
+                    .add_sized([button_width - 5.0, 15.0], Button::new("Cancel"))
+                    .clicked()
+                {
+                    self.counter.show_reset_modal = false;
+                }
+            });
+        });
+
+        if modal.should_close() {
+            self.counter.show_reset_modal = false;
+        }
+    }
+
+    /// Shows the message and user count of each thread of the selected chat
+    fn show_thread_modal(&mut self, ui: &mut Ui) {
+        let chat_name = self.counter.selected_chat_name(self.counter_chat_index);
+        let threads = self.count().threads.clone();
+
+        let modal = Modal::new(Id::new("thread_modal")).show(ui.ctx(), |ui| {
+            ui.set_width(350.0);
+            ui.vertical_centered(|ui| {
+                ui.heading(format!("Threads of {chat_name}"));
+            });
+            ui.add_space(5.0);
+
+            ScrollArea::vertical().max_height(300.0).show(ui, |ui| {
+                Grid::new("Thread Grid")
+                    .num_columns(3)
+                    .striped(true)
+                    .spacing([20.0, 5.0])
+                    .show(ui, |ui| {
+                        ui.strong("Thread");
+                        ui.strong("Messages");
+                        ui.strong("Users");
+                        ui.end_row();
+
+                        for (thread_id, stats) in threads.sorted_threads() {
+                            let thread_name =
+                                thread_id.map_or(String::from("No Thread"), |id| format!("#{id}"));
+                            ui.label(thread_name);
+                            ui.label(stats.total_message.to_string());
+                            ui.label(stats.total_user().to_string());
+                            ui.end_row();
+                        }
+                    });
+            });
+            ui.add_space(5.0);
+
+            ui.vertical_centered(|ui| {
+                if ui.button("Close").clicked() {
+                    self.counter.show_thread_modal = false;
+                }
+            });
+        });
+
+        if modal.should_close() {
+            self.counter.show_thread_modal = false;
+        }
+    }
+
+    /// Resolves the starting and ending point of every given chat to report the total messages
+    /// that would be counted
+    fn preview_range(&mut self) {
+        let selected_client = self.get_selected_session();
+
+        if selected_client.is_empty() {
+            self.process_state = ProcessState::EmptySelectedSession;
+            return;
+        }
+
+        let parsed_chat_data =
+            parse_chat_details(&self.counter.get_start_from(), &self.counter.get_end_at());
+
+        if parsed_chat_data.is_empty() {
+            self.process_state = ProcessState::InvalidStartChat;
+            return;
+        }
+
+        info!("Previewing the range of {} chats", parsed_chat_data.len());
+
+        self.counter.preview = Some(RangePreview {
+            pending: parsed_chat_data.len(),
+            ..Default::default()
+        });
+        self.is_processing = true;
+        self.process_state = ProcessState::PreviewingRange;
+
+        let client = self.tg_clients.get(&selected_client).unwrap();
+        for chat in parsed_chat_data.into_values() {
+            let client = client.clone();
+            self.runtime.spawn(async move {
+                client
+                    .start_process(ProcessStart::CheckChatExistence(
+                        chat.name(),
+                        chat.start_point(),
+                        chat.end_point(),
+                    ))
+                    .await;
+            });
+        }
+    }
+
+    /// Adds the resolved range of a chat to the preview or marks it as failed if None
+    pub fn preview_resolved(&mut self, total_message: Option<i32>) {
+        let Some(preview) = self.counter.preview.as_mut() else {
+            return;
+        };
+
+        if let Some(total_message) = total_message {
+            preview.total_message += i64::from(total_message);
+            preview.total_chats += 1;
+        } else {
+            preview.failed_chats += 1;
+        }
+        preview.pending -= 1;
+
+        if preview.pending == 0 {
+            let preview = self.counter.preview.take().unwrap();
+            self.stop_process();
+            self.process_state = ProcessState::RangePreviewed(
+                preview.total_message,
+                preview.total_chats,
+                preview.failed_chats,
+            );
+        }
+    }
+
+    /// Shows how close each session is to triggering flood wait
+    fn show_session_gauges(&self, ui: &mut Ui) {
+        ui.horizontal_wrapped(|ui| {
+            for (session, total) in &self.counter.session_messages {
+                let usage = *total as f32 / FLOOD_WAIT_THRESHOLD as f32;
+                let mut gauge = ProgressBar::new(usage.min(1.0))
+                    .desired_width(150.0)
+                    .text(format!("{session}: {total}/{FLOOD_WAIT_THRESHOLD}"));
+
+                if usage >= FLOOD_WAIT_WARNING {
+                    gauge = gauge.fill(ui.visuals().warn_fg_color);
+                }
+                ui.add(gauge).on_hover_text(format!(
+                    "Messages counted by this session. Flood wait is usually triggered after about {FLOOD_WAIT_THRESHOLD} messages"
+                ));
+            }
+        });
+    }
+
+    /// Recalculate the most active users of the ongoing chat if the interval has passed
+    fn update_leaderboard(&mut self) {
+        let outdated = self
+            .counter
+            .leaderboard_updated
+            .is_none_or(|updated| updated.elapsed() >= LEADERBOARD_INTERVAL);
+
+        if outdated {
+            self.counter.leaderboard = self.t_table().top_users(LEADERBOARD_SIZE);
+            self.counter.leaderboard_updated = Some(Instant::now());
+        }
     }
 
     fn start_counting(&mut self) {
@@ -473,20 +1204,47 @@ To count all messages in a chat, paste the very first message link or keep it em
         let start_from = self.counter.get_start_from();
         let end_at = self.counter.get_end_at();
 
-        let parsed_chat_data = parse_chat_details(&start_from, &end_at);
+        let mut parsed_chat_data = parse_chat_details(&start_from, &end_at);
 
         if parsed_chat_data.is_empty() {
             self.process_state = ProcessState::InvalidStartChat;
             return;
         }
 
-        let total_parsed = parsed_chat_data.len();
+        // Remember the range before any checkpoint changes it
+        self.counter.chat_ranges.extend(parsed_chat_data.clone());
+
+        // Chats that continue from a checkpoint keep their existing data
+        let mut to_clear = HashMap::new();
+        if self.counter.incremental {
+            let checkpoints = get_checkpoints();
+            for (name, chat) in &mut parsed_chat_data {
+                let continued = chat.end_point().is_none()
+                    && checkpoints
+                        .get(name)
+                        .is_some_and(|checkpoint| chat.set_end_point(checkpoint + 1));
+
+                if continued {
+                    info!("Counting {name} from checkpoint {:?}", chat.end_point());
+                } else {
+                    to_clear.insert(name.clone(), chat.clone());
+                }
+            }
+        } else {
+            to_clear.clone_from(&parsed_chat_data);
+        }
 
         if self.counter.retain_data {
-            self.clear_overlap(&parsed_chat_data);
+            self.clear_overlap(&to_clear);
         }
 
+        let total_new = parsed_chat_data
+            .keys()
+            .filter(|name| !self.counter.retain_data || !self.counter.contains_chat(name))
+            .count();
+
         self.counter.set_parsed_chat(parsed_chat_data);
+        self.prepare_counting();
 
         if !self.counter.retain_data {
             self.reset_counts();
@@ -494,22 +1252,80 @@ To count all messages in a chat, paste the very first message link or keep it em
             self.reset_chart();
         }
         self.initial_chart_reset();
-        self.append_structs(total_parsed, self.counter.total_chats());
+        self.append_structs(total_new, self.counter.total_chats());
+        self.process_next_count();
+
+        // Counting still runs with the filter matched as plain text
+        if !MessageFilter::is_valid_regex(&self.counter.message_filter) {
+            self.process_state = ProcessState::InvalidMessageFilter;
+        }
+    }
+
+    /// Clears the existing data of a counted chat and counts it again with its original range
+    pub fn recount_chat(&mut self, chat_name: &str) {
+        if self.get_selected_session().is_empty() {
+            self.process_state = ProcessState::EmptySelectedSession;
+            return;
+        }
+
+        let Some(chat) = self.counter.chat_ranges.get(chat_name).cloned() else {
+            return;
+        };
+
+        info!("Recounting {chat_name}");
+
+        let parsed_chat_data = HashMap::from([(chat.name(), chat)]);
+        self.clear_overlap(&parsed_chat_data);
+        self.counter.set_parsed_chat(parsed_chat_data);
+        self.prepare_counting();
+
+        self.initial_chart_reset();
+        self.append_structs(1, self.counter.total_chats());
+        // The recounted chat is added back as the last one
+        self.table_chat_index = self.counter.total_chats();
         self.process_next_count();
     }
 
+    /// Resets the state of the previous counting session before a new one starts
+    fn prepare_counting(&mut self) {
+        self.cancel_count.store(false, Ordering::Release);
+        self.clear_blacklist_undo();
+        self.counter.auto_saved = 0;
+        self.counter.capped_chats = 0;
+        self.counter.leaderboard.clear();
+        self.counter.leaderboard_updated = None;
+        self.counter.session_messages.clear();
+    }
+
     pub fn process_next_count(&mut self) {
         let target_chat = self.counter.get_parsed_chat();
 
         let Some(chat) = target_chat else {
             info!("No other chat to process.");
             self.stop_process();
-            self.process_state = ProcessState::Idle;
+            let cap = self.counter.active_cap.take();
+            self.process_state = if let Some(cap) = cap.filter(|_| self.counter.capped_chats > 0) {
+                ProcessState::MessageCapReached(cap.limit(), self.counter.capped_chats)
+            } else if self.counter.auto_saved > 0 {
+                ProcessState::DataExported(
+                    current_dir().unwrap().to_string_lossy().into(),
+                    self.counter.auto_saved,
+                )
+            } else if !MessageFilter::is_valid_regex(&self.counter.message_filter) {
+                ProcessState::InvalidMessageFilter
+            } else {
+                ProcessState::Idle
+            };
             return;
         };
 
-        self.counter.add_to_chat(chat.name());
-        let ongoing_index = self.counter.total_chats() - 1;
+        // Incremental counting adds to the existing data of the chat if there is any
+        let ongoing_index = if self.counter.contains_chat(&chat.name()) {
+            self.counter.chat_index(&chat.name())
+        } else {
+            self.counter.add_to_chat(chat.name());
+            self.counter.total_chats() - 1
+        };
 
         self.counter.set_ongoing_chat(ongoing_index);
 
@@ -525,9 +1341,23 @@ To count all messages in a chat, paste the very first message link or keep it em
         let start_num = chat.start_point();
         let end_num = chat.end_point();
 
-        let client = self.tg_clients.get(&selected_client).unwrap().clone();
+        self.t_count().count_only = self.counter.count_only;
+        self.t_count().count_threads = self.counter.count_threads;
 
-        if self.counter.use_all_sessions && self.tg_clients.len() > 1 {
+        if self.counter.stream_export {
+            self.stream_writer = create_stream_file(&chat_name);
+        }
+
+        let filter = self.counter.message_filter();
+        let sender_filter = self.counter.sender_filter();
+        let cap = MessageCap::new(&self.counter.message_cap);
+        self.counter.active_cap.clone_from(&cap);
+        let skip_service = self.counter.skip_service;
+        let skip_stickers = self.counter.skip_stickers;
+        let counting_sessions = self.counting_sessions();
+
+        if counting_sessions.len() > 1 {
+            let client = counting_sessions[0].clone();
             self.runtime.spawn(async move {
                 client
                     .start_process(ProcessStart::CheckChatExistence(
@@ -536,17 +1366,36 @@ To count all messages in a chat, paste the very first message link or keep it em
                     .await;
             });
         } else {
+            let client = self.tg_clients.get(&selected_client).unwrap().clone();
             let cancel = self.cancel_count.clone();
             self.runtime.spawn(async move {
                 client
                     .start_process(ProcessStart::StartCount(
-                        chat_name, start_num, end_num, false, cancel,
+                        chat_name,
+                        start_num,
+                        end_num,
+                        false,
+                        cancel,
+                        filter,
+                        sender_filter,
+                        cap,
+                        skip_service,
+                        skip_stickers,
                     ))
                     .await;
             });
         }
     }
 
+    /// Returns the sessions that are selected for multi session counting
+    pub fn counting_sessions(&self) -> Vec<TGClient> {
+        self.tg_clients
+            .values()
+            .filter(|client| self.counter.enabled_sessions.contains(&client.name()))
+            .cloned()
+            .collect()
+    }
+
     /// Returns the session name that is selected on the combo box
     pub fn get_selected_session(&self) -> String {
         let all_sessions = self.get_session_names();
@@ -1,4 +1,6 @@
-use eframe::egui::{vec2, Align, Button, Checkbox, Context, Grid, Label, Layout, TextEdit, Ui};
+use eframe::egui::{
+    vec2, Align, Button, Checkbox, Context, Grid, Label, Layout, ScrollArea, TextEdit, Ui,
+};
 use grammers_client::types::{LoginToken, PasswordToken};
 use std::sync::Arc;
 use tokio::sync::Mutex;
@@ -16,6 +18,8 @@ pub struct SessionData {
     is_temporary: bool,
     password_token: Option<Arc<Mutex<PasswordToken>>>,
     tg_code_token: Option<Arc<Mutex<LoginToken>>>,
+    /// Name of the session that is being reconnected
+    reconnecting: Option<String>,
 }
 
 impl SessionData {
@@ -55,6 +59,14 @@ impl SessionData {
         self.password_token.clone().unwrap()
     }
 
+    pub fn is_reconnecting(&self) -> bool {
+        self.reconnecting.is_some()
+    }
+
+    pub fn take_reconnecting(&mut self) -> Option<String> {
+        self.reconnecting.take()
+    }
+
     pub fn reset_data(&mut self) {
         self.session_name = String::new();
         self.phone_number = String::new();
@@ -218,6 +230,61 @@ If yes, it will try to log out before the app is closed and no session file will
                 }
             }
         });
+
+        if self.tg_clients.is_empty() {
+            return;
+        }
+
+        ui.add_space(20.0);
+        ui.separator();
+        ui.label("Connected Sessions:");
+
+        let mut to_reconnect = None;
+        ScrollArea::vertical().max_height(100.0).show(ui, |ui| {
+            Grid::new("Connected Session Grid")
+                .num_columns(2)
+                .striped(true)
+                .spacing([20.0, 5.0])
+                .show(ui, |ui| {
+                    for (name, client) in &self.tg_clients {
+                        ui.label(name);
+                        let enabled = !self.is_processing && !client.is_temporary();
+                        if ui
+                            .add_enabled(enabled, Button::new("Reconnect"))
+                            .on_hover_text("Connect to this session again, useful after the network connection was lost")
+                            .on_disabled_hover_text("Temporary sessions cannot be reconnected as they do not have a session file")
+                            .clicked()
+                        {
+                            to_reconnect = Some(name.clone());
+                        }
+                        ui.end_row();
+                    }
+                });
+        });
+
+        if let Some(name) = to_reconnect {
+            self.reconnect_session(name, ui.ctx().clone());
+        }
+    }
+
+    /// Starts a thread to connect to an existing session again. The new client replaces the
+    /// existing one once connected
+    fn reconnect_session(&mut self, name: String, context: Context) {
+        let sender_clone = self.tg_sender.clone();
+        let session_file = format!("{name}.session");
+
+        self.is_processing = true;
+        self.process_state = ProcessState::ReconnectingSession(name.clone());
+        self.session.reconnecting = Some(name);
+
+        self.runtime.spawn(async move {
+            start_process(
+                NewProcess::InitialSessionConnect(vec![session_file]),
+                sender_clone,
+                context,
+            )
+            .await;
+        });
     }
 
     /// Starts a thread to send a Telegram login code to the phone number
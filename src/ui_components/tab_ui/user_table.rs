@@ -1,34 +1,89 @@
-use chrono::{NaiveDate, NaiveDateTime};
+use chrono::{Datelike, NaiveDate, NaiveDateTime};
 use eframe::egui::{
-    Align, Button, ComboBox, Key, Layout, Response, RichText, SelectableLabel, Sense, Ui,
+    vec2, Align, Button, Checkbox, ComboBox, Event, Grid, Id, Key, Label, Layout, Modal, Rangef,
+    Rect, Response, RichText, ScrollArea, SelectableLabel, Sense, Stroke, TextEdit, Ui, UiBuilder,
 };
 use egui_extras::{Column, DatePickerButton};
 use egui_selectable_table::{
     ColumnOperations, ColumnOrdering, SelectableRow, SelectableTable, SortOrder,
 };
-use grammers_client::types::{Chat, Message};
-use log::info;
-use serde::Serialize;
-use std::collections::{HashMap, HashSet};
+use grammers_client::types::{Chat, Media, Message};
+use log::{error, info};
+use serde::{Deserialize, Serialize};
+use std::cmp::Ordering;
+use std::collections::{HashMap, HashSet, VecDeque};
 use std::env::current_dir;
+use std::hash::{DefaultHasher, Hash, Hasher};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
 use strum::IntoEnumIterator;
 
+use crate::tg_handler::ProcessStart;
 use crate::ui_components::processor::{
-    ColumnName, DateNavigator, NavigationType, PackedBlacklistedUser, PackedWhitelistedUser,
-    ProcessState,
+    CharCountConfig, CharCountMode, CharCounts, ColumnName, CounterCounts, CsvDelimiter,
+    DateFormat, DateNavigator, NavigationType, PackedBlacklistedUser, PackedWhitelistedUser,
+    ProcessState, UndoAction,
 };
-use crate::ui_components::widgets::{AnimatedLabel, RowLabel};
+use crate::ui_components::widgets::{paint_sparkline, sparkline_text, AnimatedLabel, RowLabel};
 use crate::ui_components::MainWindow;
-use crate::utils::{entry_insert_user, export_table_data, to_chart_name};
+use crate::utils::{
+    display_name, entry_insert_user, export_date_totals, export_table_data, is_rtl_text,
+    is_sticker_or_gif, rtl_display_text, save_settings, table_to_markdown, to_chart_name,
+    user_mention, ANONYMOUS_LABEL,
+};
+
+/// Number of the newest dates kept as they are when old dates are compacted
+const MAX_DAILY_DATES: usize = 180;
+/// How long a newly added row stays highlighted
+const NEW_ROW_HIGHLIGHT: Duration = Duration::from_secs(2);
+/// Separates the session names when a user was seen by multiple sessions
+const SESSION_SEPARATOR: &str = ", ";
+/// Maximum number of points in the activity sparkline of a row
+const SPARKLINE_POINTS: usize = 30;
+/// Number of the latest message texts of a user that are checked for duplicates
+const DUPLICATE_HISTORY: usize = 100;
+/// Height of the footer row with the column totals below the table
+const FOOTER_HEIGHT: f32 = 20.0;
+/// Whitelisting more displayed rows than this at once asks for confirmation first
+const WHITELIST_CONFIRM_LIMIT: usize = 50;
 
-#[derive(Default)]
 pub struct Config {
     whitelist_rows: bool,
     blacklisted_rows: bool,
     copy_selected: bool,
+    copy_markdown: bool,
+    copy_json: bool,
+    copy_mentions: bool,
+    /// Column whose values of all displayed rows should be copied
+    copy_column: Option<ColumnName>,
+    activity_colors: bool,
+    /// Columns the table is sorted by, used to show the sort direction and priority in the header
+    sort_columns: Vec<(ColumnName, SortOrder)>,
+    /// Header that was clicked and whether Shift was held to add it as a tiebreaker
+    sort_clicked: Option<(ColumnName, bool)>,
+    /// Horizontal position of each header in the last frame, used to line up the footer
+    header_ranges: HashMap<ColumnName, Rangef>,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            whitelist_rows: false,
+            blacklisted_rows: false,
+            copy_selected: false,
+            copy_markdown: false,
+            copy_json: false,
+            copy_mentions: false,
+            copy_column: None,
+            activity_colors: true,
+            sort_columns: vec![(ColumnName::default(), SortOrder::Ascending)],
+            sort_clicked: None,
+            header_ranges: HashMap::new(),
+        }
+    }
 }
 
-#[derive(Clone, Serialize)]
+#[derive(Clone, Serialize, Deserialize)]
 pub struct UserRowData {
     name: String,
     username: String,
@@ -36,15 +91,56 @@ pub struct UserRowData {
     total_message: u32,
     total_word: u32,
     total_char: u32,
-    average_word: u32,
-    average_char: u32,
+    average_word: f32,
+    average_char: f32,
+    #[serde(default)]
+    photo_count: u32,
+    #[serde(default)]
+    video_count: u32,
+    #[serde(default)]
+    file_count: u32,
+    /// Number of stickers and GIFs sent
+    #[serde(default)]
+    sticker_count: u32,
+    /// Number of messages that were edited after being sent
+    #[serde(default)]
+    edited_count: u32,
+    /// Number of messages with the same text as another recent message of the user
+    #[serde(default)]
+    duplicate_count: u32,
     first_seen: NaiveDateTime,
     last_seen: NaiveDateTime,
     whitelisted: bool,
-    #[serde(skip_serializing)]
+    #[serde(skip)]
+    is_bot: bool,
+    /// Whether the user is an admin or the owner of the chat, known after the admins are fetched
+    #[serde(skip)]
+    is_admin: bool,
+    #[serde(skip)]
     belongs_to: Option<Chat>,
-    #[serde(skip_serializing)]
+    #[serde(default)]
     seen_by: String,
+    /// Total message of this row relative to the most active row, between 0.0 and 1.0
+    #[serde(skip)]
+    activity: f32,
+    /// Percentage of the messages of all displayed rows that were sent by this user
+    #[serde(skip)]
+    message_share: f32,
+    /// Char count in every mode, used to recalculate `total_char` when the mode changes
+    #[serde(skip)]
+    char_counts: CharCounts,
+    /// When this user first showed up in the table, used to highlight new rows
+    #[serde(skip)]
+    added_at: Option<Instant>,
+    /// Messages in the selected date range split into equal periods, used for the sparkline
+    #[serde(skip)]
+    activity_points: Vec<u32>,
+    /// Columns to sort by in order of priority, shared by all rows of the table
+    #[serde(skip)]
+    sort_columns: Arc<[(ColumnName, SortOrder)]>,
+    /// How the first and last seen dates are shown
+    #[serde(skip)]
+    date_format: DateFormat,
 }
 
 impl ColumnOperations<UserRowData, ColumnName, Config> for ColumnName {
@@ -54,20 +150,30 @@ impl ColumnOperations<UserRowData, ColumnName, Config> for ColumnName {
             ColumnName::Username => row.username.to_string(),
             ColumnName::UserID => row.id.to_string(),
             ColumnName::TotalMessage => row.total_message.to_string(),
+            ColumnName::MessageShare => format!("{:.1}%", row.message_share),
             ColumnName::TotalWord => row.total_word.to_string(),
             ColumnName::TotalChar => row.total_char.to_string(),
-            ColumnName::AverageWord => row.average_word.to_string(),
-            ColumnName::AverageChar => row.average_char.to_string(),
-            ColumnName::FirstMessageSeen => row.first_seen.to_string(),
-            ColumnName::LastMessageSeen => row.last_seen.to_string(),
+            ColumnName::AverageWord => format!("{:.1}", row.average_word),
+            ColumnName::AverageChar => format!("{:.1}", row.average_char),
+            ColumnName::PhotoCount => row.photo_count.to_string(),
+            ColumnName::VideoCount => row.video_count.to_string(),
+            ColumnName::FileCount => row.file_count.to_string(),
+            ColumnName::StickerCount => row.sticker_count.to_string(),
+            ColumnName::Edited => row.edited_count.to_string(),
+            ColumnName::Duplicate => row.duplicate_count.to_string(),
+            ColumnName::FirstMessageSeen => row.date_format.format_datetime(&row.first_seen),
+            ColumnName::LastMessageSeen => row.date_format.format_datetime(&row.last_seen),
+            ColumnName::Activity => sparkline_text(&row.activity_points),
             ColumnName::Whitelisted => row.whitelisted.to_string(),
+            ColumnName::Admin => row.is_admin.to_string(),
+            ColumnName::SeenBy => row.seen_by.to_string(),
         }
     }
     fn create_header(
         &self,
         ui: &mut eframe::egui::Ui,
-        sort_order: Option<SortOrder>,
-        _table: &mut SelectableTable<UserRowData, ColumnName, Config>,
+        _sort_order: Option<SortOrder>,
+        table: &mut SelectableTable<UserRowData, ColumnName, Config>,
     ) -> Option<Response> {
         let mut label_text = self.to_string();
         let hover_text = match self {
@@ -81,6 +187,10 @@ impl ColumnOperations<UserRowData, ColumnName, Config> for ColumnName {
             ColumnName::TotalMessage => {
                 "Total messages sent by the user. Click to sort by total message".to_string()
             }
+            ColumnName::MessageShare => {
+                "Share of the messages of all displayed users in the selected date range that were sent by the user. Click to sort by share"
+                    .to_string()
+            }
             ColumnName::TotalWord => {
                 "Total words in the messages. Click to sort by total words".to_string()
             }
@@ -94,6 +204,26 @@ impl ColumnOperations<UserRowData, ColumnName, Config> for ColumnName {
                 "Average number of characters per message. Click to sort by average characters"
                     .to_string()
             }
+            ColumnName::PhotoCount => {
+                "Total photos sent by the user. Click to sort by photos".to_string()
+            }
+            ColumnName::VideoCount => {
+                "Total videos sent by the user. Click to sort by videos".to_string()
+            }
+            ColumnName::FileCount => {
+                "Total files and other documents sent by the user. Click to sort by files"
+                    .to_string()
+            }
+            ColumnName::StickerCount => {
+                "Total stickers and GIFs sent by the user. Click to sort by stickers".to_string()
+            }
+            ColumnName::Edited => {
+                "Total messages that were edited by the user. Click to sort by edited messages"
+                    .to_string()
+            }
+            ColumnName::Duplicate => {
+                format!("Messages with the same text as one of the last {DUPLICATE_HISTORY} messages of the user. Click to sort by duplicates")
+            }
 
             ColumnName::FirstMessageSeen => {
                 "The day the first message that was sent by this user was observed".to_string()
@@ -101,16 +231,39 @@ impl ColumnOperations<UserRowData, ColumnName, Config> for ColumnName {
             ColumnName::LastMessageSeen => {
                 "The day the last message that was sent by this user was observed".to_string()
             }
+            ColumnName::Activity => {
+                "Messages sent by the user over the selected date range. Click to sort by total message"
+                    .to_string()
+            }
             ColumnName::Whitelisted => {
                 "Whether this user is whitelisted. Click to sort by whitelist".to_string()
             }
+            ColumnName::Admin => {
+                "Whether this user is an admin or the owner of the chat. Available after fetching the admins. Click to sort by admin"
+                    .to_string()
+            }
+            ColumnName::SeenBy => {
+                "The sessions that observed this user. Click to sort by session".to_string()
+            }
         };
 
-        let is_selected = if let Some(direction) = sort_order {
-            match direction {
+        table
+            .config
+            .header_ranges
+            .insert(*self, ui.max_rect().x_range());
+
+        let sort_columns = &table.config.sort_columns;
+        let sort_position = sort_columns.iter().position(|(column, _)| column == self);
+
+        let is_selected = if let Some(position) = sort_position {
+            match sort_columns[position].1 {
                 SortOrder::Ascending => label_text.push('↓'),
                 SortOrder::Descending => label_text.push('↑'),
             }
+            // Show the priority only when sorting by multiple columns
+            if sort_columns.len() > 1 {
+                label_text.push_str(&(position + 1).to_string());
+            }
             true
         } else {
             false
@@ -123,8 +276,28 @@ impl ColumnOperations<UserRowData, ColumnName, Config> for ColumnName {
                 ui.available_size(),
                 SelectableLabel::new(is_selected, label_text),
             )
-            .on_hover_text(hover_text);
-        Some(response)
+            .on_hover_text(format!(
+                "{hover_text}\n\nShift + click to sort by this column as a tiebreaker"
+            ));
+
+        // Sorting is handled by the app so that multiple columns can be used. The response is
+        // not returned to prevent the table from sorting on its own
+        if response.clicked() {
+            let add_tiebreaker = ui.input(|i| i.modifiers.shift);
+            table.config.sort_clicked = Some((*self, add_tiebreaker));
+        }
+
+        response.context_menu(|ui| {
+            if ui
+                .button("Copy this column")
+                .on_hover_text("Copy the values of this column from all displayed rows")
+                .clicked()
+            {
+                table.config.copy_column = Some(*self);
+                ui.close_menu();
+            }
+        });
+        None
     }
     fn create_table_row(
         &self,
@@ -146,35 +319,104 @@ impl ColumnOperations<UserRowData, ColumnName, Config> for ColumnName {
             }
             ColumnName::UserID => row_data.id.to_string(),
             ColumnName::TotalMessage => row_data.total_message.to_string(),
+            ColumnName::MessageShare => format!("{:.1}%", row_data.message_share),
             ColumnName::TotalWord => row_data.total_word.to_string(),
             ColumnName::TotalChar => row_data.total_char.to_string(),
-            ColumnName::AverageWord => row_data.average_word.to_string(),
-            ColumnName::AverageChar => row_data.average_char.to_string(),
-            ColumnName::FirstMessageSeen => row_data.first_seen.to_string(),
-            ColumnName::LastMessageSeen => row_data.last_seen.to_string(),
+            ColumnName::AverageWord => format!("{:.1}", row_data.average_word),
+            ColumnName::AverageChar => format!("{:.1}", row_data.average_char),
+            ColumnName::PhotoCount => row_data.photo_count.to_string(),
+            ColumnName::VideoCount => row_data.video_count.to_string(),
+            ColumnName::FileCount => row_data.file_count.to_string(),
+            ColumnName::StickerCount => row_data.sticker_count.to_string(),
+            ColumnName::Edited => row_data.edited_count.to_string(),
+            ColumnName::Duplicate => row_data.duplicate_count.to_string(),
+            ColumnName::FirstMessageSeen => {
+                row_data.date_format.format_datetime(&row_data.first_seen)
+            }
+            ColumnName::LastMessageSeen => {
+                row_data.date_format.format_datetime(&row_data.last_seen)
+            }
+            ColumnName::Activity => String::new(),
             ColumnName::Whitelisted => {
                 let text = if row_data.whitelisted { "Yes" } else { "No" };
                 text.to_string()
             }
+            ColumnName::Admin => {
+                let text = if row_data.is_admin { "Yes" } else { "No" };
+                text.to_string()
+            }
+            ColumnName::SeenBy => {
+                show_tooltip = row_data.seen_by.contains(SESSION_SEPARATOR);
+                row_data.seen_by.clone()
+            }
         };
         let is_selected = column_selected;
         let is_whitelisted = row_data.whitelisted;
+        let intensity = if table.config.activity_colors {
+            row_data.activity
+        } else {
+            0.0
+        };
+
+        // Fade the highlight out and keep repainting until it is gone
+        let highlight = row_data.added_at.map_or(0.0, |added_at| {
+            1.0 - added_at.elapsed().as_secs_f32() / NEW_ROW_HIGHLIGHT.as_secs_f32()
+        });
+        if highlight > 0.0 {
+            ui.ctx().request_repaint();
+        }
 
         let mut label = ui
             .add_sized(
                 ui.available_size(),
-                RowLabel::new(is_selected, is_whitelisted, &row_text),
+                RowLabel::new(is_selected, is_whitelisted, intensity, highlight, &row_text),
             )
             .interact(Sense::drag());
 
         if show_tooltip {
-            label = label.on_hover_text(row_text);
+            let tooltip_text = if is_rtl_text(&row_text) {
+                rtl_display_text(&row_text)
+            } else {
+                row_text
+            };
+            label = label.on_hover_text(tooltip_text);
         };
+
+        // Only the visible rows reach here so the sparkline is drawn only when it can be seen
+        if *self == ColumnName::Activity {
+            let stroke = Stroke::new(1.5, ui.visuals().text_color());
+            let rect = label.rect.shrink2(vec2(6.0, 4.0));
+            paint_sparkline(ui.painter(), rect, &row_data.activity_points, stroke);
+        }
         label.context_menu(|ui| {
             if ui.button("Copy selected rows").clicked() {
                 table.config.copy_selected = true;
                 ui.close_menu();
             };
+            if ui
+                .button("Copy as Markdown")
+                .on_hover_text("Copy the selected rows, or all displayed rows if none are selected, as a Markdown table")
+                .clicked()
+            {
+                table.config.copy_markdown = true;
+                ui.close_menu();
+            };
+            if ui
+                .button("Copy as JSON")
+                .on_hover_text("Copy the selected rows as a JSON array")
+                .clicked()
+            {
+                table.config.copy_json = true;
+                ui.close_menu();
+            };
+            if ui
+                .button("Copy as Telegram mentions")
+                .on_hover_text("Copy the selected users as mentions that can be pasted into a Telegram message")
+                .clicked()
+            {
+                table.config.copy_mentions = true;
+                ui.close_menu();
+            };
             if ui.button("Whitelist selected rows").clicked() {
                 table.config.whitelist_rows = true;
                 ui.close_menu();
@@ -184,27 +426,130 @@ impl ColumnOperations<UserRowData, ColumnName, Config> for ColumnName {
                 table.config.blacklisted_rows = true;
                 ui.close_menu();
             };
+            ui.separator();
+            if ui.button("Copy user ID").clicked() {
+                ui.ctx().copy_text(row_data.id.to_string());
+                ui.close_menu();
+            };
+
+            let has_username = row_data.username != "Empty";
+            if ui
+                .add_enabled(has_username, Button::new("Open in Telegram"))
+                .on_disabled_hover_text("This user does not have a username")
+                .clicked()
+            {
+                let link = format!("https://t.me/{}", row_data.username);
+                if let Err(e) = open::that(link) {
+                    error!("Failed to open the Telegram link. Error: {e}");
+                }
+                ui.close_menu();
+            };
         });
         label
     }
 }
 
 impl ColumnOrdering<UserRowData> for ColumnName {
-    fn order_by(&self, row_1: &UserRowData, row_2: &UserRowData) -> std::cmp::Ordering {
-        match self {
-            ColumnName::Name => row_1.name.cmp(&row_2.name),
-            ColumnName::Username => row_1.username.cmp(&row_2.username),
-            ColumnName::UserID => row_1.id.cmp(&row_2.id),
-            ColumnName::TotalMessage => row_1.total_message.cmp(&row_2.total_message),
-            ColumnName::TotalWord => row_1.total_word.cmp(&row_2.total_word),
-            ColumnName::TotalChar => row_1.total_char.cmp(&row_2.total_char),
-            ColumnName::AverageWord => row_1.average_word.cmp(&row_2.average_word),
-            ColumnName::AverageChar => row_1.average_char.cmp(&row_2.average_char),
-            ColumnName::FirstMessageSeen => row_1.first_seen.cmp(&row_2.first_seen),
-            ColumnName::LastMessageSeen => row_1.last_seen.cmp(&row_2.last_seen),
-            ColumnName::Whitelisted => row_1.whitelisted.cmp(&row_2.whitelisted),
+    fn order_by(&self, row_1: &UserRowData, row_2: &UserRowData) -> Ordering {
+        if row_1.sort_columns.is_empty() {
+            return compare_column(*self, row_1, row_2);
+        }
+
+        // Each following column is only used when the rows are equal in the previous ones
+        row_1
+            .sort_columns
+            .iter()
+            .fold(Ordering::Equal, |ordering, (column, order)| {
+                ordering.then_with(|| {
+                    let ordering = compare_column(*column, row_1, row_2);
+                    match order {
+                        SortOrder::Ascending => ordering,
+                        SortOrder::Descending => ordering.reverse(),
+                    }
+                })
+            })
+    }
+}
+
+/// Replaces the names, usernames, IDs and session names of the rows with pseudonyms. The numbers
+/// are assigned by the hash of the user ID so the same user always gets the same pseudonym within
+/// an export
+fn anonymize_rows(rows: &mut [UserRowData]) {
+    let mut hashed_ids: Vec<(u64, i64)> = rows
+        .iter()
+        .filter(|row| row.name != ANONYMOUS_LABEL)
+        .map(|row| {
+            let mut hasher = DefaultHasher::new();
+            row.id.hash(&mut hasher);
+            (hasher.finish(), row.id)
+        })
+        .collect();
+    hashed_ids.sort_unstable();
+    hashed_ids.dedup();
+
+    let pseudonyms: HashMap<i64, usize> = hashed_ids
+        .into_iter()
+        .enumerate()
+        .map(|(index, (_, id))| (id, index + 1))
+        .collect();
+
+    for row in rows.iter_mut() {
+        if let Some(number) = pseudonyms.get(&row.id) {
+            row.name = format!("User #{number}");
+            row.username = "Empty".to_string();
+            row.id = *number as i64;
         }
     }
+
+    // The session names are the names given to the sessions by the user
+    let mut sessions: Vec<String> = rows
+        .iter()
+        .flat_map(|row| row.seen_by.split(SESSION_SEPARATOR))
+        .filter(|session| !session.is_empty())
+        .map(ToString::to_string)
+        .collect();
+    sessions.sort_unstable();
+    sessions.dedup();
+
+    for row in rows {
+        row.seen_by = row
+            .seen_by
+            .split(SESSION_SEPARATOR)
+            .filter(|session| !session.is_empty())
+            .map(|session| {
+                let number = sessions.binary_search_by(|name| name.as_str().cmp(session));
+                format!("Session #{}", number.unwrap_or_default() + 1)
+            })
+            .collect::<Vec<String>>()
+            .join(SESSION_SEPARATOR);
+    }
+}
+
+/// Compares two rows by the value of a single column in ascending order
+fn compare_column(column: ColumnName, row_1: &UserRowData, row_2: &UserRowData) -> Ordering {
+    match column {
+        ColumnName::Name => row_1.name.cmp(&row_2.name),
+        ColumnName::Username => row_1.username.cmp(&row_2.username),
+        ColumnName::UserID => row_1.id.cmp(&row_2.id),
+        ColumnName::TotalMessage => row_1.total_message.cmp(&row_2.total_message),
+        ColumnName::MessageShare => row_1.message_share.total_cmp(&row_2.message_share),
+        ColumnName::TotalWord => row_1.total_word.cmp(&row_2.total_word),
+        ColumnName::TotalChar => row_1.total_char.cmp(&row_2.total_char),
+        ColumnName::AverageWord => row_1.average_word.total_cmp(&row_2.average_word),
+        ColumnName::AverageChar => row_1.average_char.total_cmp(&row_2.average_char),
+        ColumnName::PhotoCount => row_1.photo_count.cmp(&row_2.photo_count),
+        ColumnName::VideoCount => row_1.video_count.cmp(&row_2.video_count),
+        ColumnName::FileCount => row_1.file_count.cmp(&row_2.file_count),
+        ColumnName::StickerCount => row_1.sticker_count.cmp(&row_2.sticker_count),
+        ColumnName::Edited => row_1.edited_count.cmp(&row_2.edited_count),
+        ColumnName::Duplicate => row_1.duplicate_count.cmp(&row_2.duplicate_count),
+        ColumnName::FirstMessageSeen => row_1.first_seen.cmp(&row_2.first_seen),
+        ColumnName::LastMessageSeen => row_1.last_seen.cmp(&row_2.last_seen),
+        ColumnName::Activity => row_1.total_message.cmp(&row_2.total_message),
+        ColumnName::Whitelisted => row_1.whitelisted.cmp(&row_2.whitelisted),
+        ColumnName::Admin => row_1.is_admin.cmp(&row_2.is_admin),
+        ColumnName::SeenBy => row_1.seen_by.cmp(&row_2.seen_by),
+    }
 }
 
 impl UserRowData {
@@ -218,6 +563,7 @@ impl UserRowData {
         seen_by: String,
     ) -> Self {
         let username = username.to_string();
+        let is_bot = matches!(&belongs_to, Some(Chat::User(user)) if user.is_bot());
 
         UserRowData {
             name: name.to_string(),
@@ -226,13 +572,28 @@ impl UserRowData {
             total_message: 0,
             total_word: 0,
             total_char: 0,
-            average_word: 0,
-            average_char: 0,
+            average_word: 0.0,
+            average_char: 0.0,
+            photo_count: 0,
+            video_count: 0,
+            file_count: 0,
+            sticker_count: 0,
+            edited_count: 0,
+            duplicate_count: 0,
             first_seen: date,
             last_seen: date,
             whitelisted,
+            is_bot,
+            is_admin: false,
             belongs_to,
             seen_by,
+            activity: 0.0,
+            message_share: 0.0,
+            char_counts: CharCounts::default(),
+            added_at: None,
+            activity_points: Vec::new(),
+            sort_columns: Arc::default(),
+            date_format: DateFormat::default(),
         }
     }
 
@@ -249,13 +610,80 @@ impl UserRowData {
     /// Increment total word count by `word_num`
     fn increment_total_word(&mut self, word_num: u32) {
         self.total_word += word_num;
-        self.average_word = self.total_word / self.total_message;
+        self.average_word = self.total_word as f32 / self.total_message as f32;
+    }
+
+    /// Add the char counts of a message and update the total char count based on the config
+    fn increment_char_counts(&mut self, char_counts: CharCounts, config: CharCountConfig) {
+        self.char_counts.add(char_counts);
+        self.set_char_config(config);
+    }
+
+    /// Recalculate total and average char count for the given config
+    fn set_char_config(&mut self, config: CharCountConfig) {
+        self.total_char = self.char_counts.get(config);
+        self.average_char = self.total_char as f32 / self.total_message as f32;
+    }
+
+    /// Increment the media counts by the given amounts
+    fn increment_media(&mut self, photo: u32, video: u32, file: u32) {
+        self.photo_count += photo;
+        self.video_count += video;
+        self.file_count += file;
     }
 
-    /// Increment total char count by `char_num`
-    fn increment_total_char(&mut self, char_num: u32) {
-        self.total_char += char_num;
-        self.average_char = self.total_char / self.total_message;
+    /// Increment the sticker and GIF count by 1
+    fn increment_sticker(&mut self) {
+        self.sticker_count += 1;
+    }
+
+    /// Increment the edited message count by 1
+    fn increment_edited(&mut self) {
+        self.edited_count += 1;
+    }
+
+    /// Increment the duplicate message count by 1
+    fn increment_duplicate(&mut self) {
+        self.duplicate_count += 1;
+    }
+
+    /// Add the counts of another row of the same user to this row
+    fn merge_row(&mut self, row: &UserRowData, config: CharCountConfig) {
+        if self.first_seen > row.first_seen {
+            self.set_first_seen(row.first_seen);
+        }
+
+        if self.last_seen < row.last_seen {
+            self.set_last_seen(row.last_seen);
+        }
+
+        self.increase_message_by(row.total_message);
+        self.increment_total_word(row.total_word);
+        self.increment_char_counts(row.char_counts, config);
+        self.increment_media(row.photo_count, row.video_count, row.file_count);
+        self.sticker_count += row.sticker_count;
+        self.edited_count += row.edited_count;
+        self.duplicate_count += row.duplicate_count;
+
+        for session in row.seen_by.split(SESSION_SEPARATOR) {
+            if !self
+                .seen_by
+                .split(SESSION_SEPARATOR)
+                .any(|seen| seen == session)
+            {
+                self.seen_by.push_str(SESSION_SEPARATOR);
+                self.seen_by.push_str(session);
+            }
+        }
+    }
+
+    /// The first session that saw this user, used to load the user back when saved
+    fn primary_session(&self) -> String {
+        self.seen_by
+            .split(SESSION_SEPARATOR)
+            .next()
+            .unwrap_or_default()
+            .to_string()
     }
 
     /// Update the date this user was first seen in the chat
@@ -269,6 +697,29 @@ impl UserRowData {
     }
 }
 
+/// Message and user totals of a single date, one row of the date export
+#[derive(Serialize)]
+pub struct DateTotals {
+    date: NaiveDate,
+    total_message: u32,
+    unique_users: usize,
+    whitelisted_message: u32,
+}
+
+/// A user that was found in more than one counted chat
+pub struct OverlapUser {
+    name: String,
+    id: i64,
+    /// The chats the user was found in with their message count in each
+    chats: Vec<(String, u32)>,
+}
+
+impl OverlapUser {
+    fn total_message(&self) -> u32 {
+        self.chats.iter().map(|(_, count)| count).sum()
+    }
+}
+
 pub struct UserTableData {
     /// Key: The Date where at least one message/User was found
     /// Value: A hashmap of the founded User with their user id as the key
@@ -279,15 +730,53 @@ pub struct UserTableData {
     total_whitelisted_user: u32,
     total_message: u32,
     total_whitelisted_message: u32,
-    reload_count: u8,
+    reload_count: u32,
+    hidden_columns: HashSet<ColumnName>,
+    /// Row index to scroll to on the next frame and where to align it
+    scroll_to_row: Option<(usize, Option<Align>)>,
+    /// Cell moved with the arrow keys as the displayed row index and the visible column index
+    focused_cell: Option<(usize, usize)>,
+    /// Cell where the Shift + arrow key selection started
+    selection_anchor: Option<(usize, usize)>,
+    char_config: CharCountConfig,
+    /// Whether the rows of bot users are excluded from the table
+    skip_bots: bool,
+    /// IDs of the admins and the owner of the chat, empty until they are fetched
+    admins: HashSet<i64>,
+    /// Whether the rows of the admins are excluded from the table
+    skip_admins: bool,
+    /// Users that are still members of the chat. Rows of other users are hidden when set
+    members: Option<HashSet<i64>>,
+    /// Columns to sort by in order of priority with their direction
+    sort_columns: Vec<(ColumnName, SortOrder)>,
+    /// User IDs that were in the table during the last `create_rows` call
+    previous_ids: HashSet<i64>,
+    /// When each new user was added to the table, removed once the highlight is over
+    added_at: HashMap<i64, Instant>,
+    /// Whether to ask for confirmation before whitelisting all displayed rows
+    show_whitelist_modal: bool,
+    /// Hashes of the latest message texts of each user, used to detect duplicate messages
+    recent_texts: HashMap<i64, VecDeque<u64>>,
+    date_format: DateFormat,
+    /// Whether dates older than the newest `MAX_DAILY_DATES` are merged into monthly buckets
+    compact_dates: bool,
+    /// Whether any date of this table was merged into a monthly bucket
+    dates_compacted: bool,
 }
 
 impl Default for UserTableData {
     fn default() -> Self {
-        let table = SelectableTable::new(ColumnName::iter().collect())
-            .auto_scroll()
-            .serial_column()
-            .horizontal_scroll();
+        let hidden_columns = HashSet::from([
+            ColumnName::PhotoCount,
+            ColumnName::VideoCount,
+            ColumnName::FileCount,
+            ColumnName::StickerCount,
+            ColumnName::Edited,
+            ColumnName::Duplicate,
+            ColumnName::MessageShare,
+            ColumnName::Admin,
+        ]);
+        let table = Self::build_table(&hidden_columns);
         Self {
             user_data: HashMap::new(),
             table,
@@ -296,12 +785,195 @@ impl Default for UserTableData {
             total_message: 0,
             total_whitelisted_user: 0,
             reload_count: 0,
+            hidden_columns,
+            scroll_to_row: None,
+            focused_cell: None,
+            selection_anchor: None,
+            char_config: CharCountConfig::default(),
+            skip_bots: false,
+            admins: HashSet::new(),
+            skip_admins: false,
+            members: None,
+            sort_columns: vec![(ColumnName::default(), SortOrder::Ascending)],
+            previous_ids: HashSet::new(),
+            added_at: HashMap::new(),
+            show_whitelist_modal: false,
+            recent_texts: HashMap::new(),
+            date_format: DateFormat::default(),
+            compact_dates: false,
+            dates_compacted: false,
         }
     }
 }
 
 impl UserTableData {
-    pub fn reload_count(&self) -> u8 {
+    /// Create a new selectable table with only the columns that are not hidden
+    fn build_table(
+        hidden_columns: &HashSet<ColumnName>,
+    ) -> SelectableTable<UserRowData, ColumnName, Config> {
+        let columns = ColumnName::iter()
+            .filter(|column| !hidden_columns.contains(column))
+            .collect();
+        SelectableTable::new(columns)
+            .auto_scroll()
+            .serial_column()
+            .horizontal_scroll()
+    }
+
+    /// The columns that are currently visible in the table, in order
+    fn visible_columns(&self) -> Vec<ColumnName> {
+        ColumnName::iter()
+            .filter(|column| !self.hidden_columns.contains(column))
+            .collect()
+    }
+
+    /// Show or hide a column and rebuild the table with the new set of columns
+    fn toggle_column(&mut self, column: ColumnName) {
+        if !self.hidden_columns.remove(&column) {
+            self.hidden_columns.insert(column);
+        }
+        let activity_colors = self.table.config.activity_colors;
+        self.table = Self::build_table(&self.hidden_columns);
+        self.table.config.activity_colors = activity_colors;
+        self.table
+            .config
+            .sort_columns
+            .clone_from(&self.sort_columns);
+        self.create_rows();
+    }
+
+    /// The first selected cell as the displayed row index and the visible column index
+    fn first_selected_cell(&self) -> Option<(usize, usize)> {
+        let columns = self.visible_columns();
+        self.table
+            .get_displayed_rows()
+            .iter()
+            .enumerate()
+            .find_map(|(index, row)| {
+                columns
+                    .iter()
+                    .position(|column| row.selected_columns.contains(column))
+                    .map(|column_index| (index, column_index))
+            })
+    }
+
+    /// Moves the focused cell by the given offset. If `extend` is true, every cell between the
+    /// anchor and the focused cell is selected, otherwise only the focused cell
+    fn move_focus(&mut self, row_offset: isize, column_offset: isize, extend: bool) {
+        let total_rows = self.table.total_displayed_rows();
+        let total_columns = self.visible_columns().len();
+        if total_rows == 0 || total_columns == 0 {
+            return;
+        }
+
+        let start = self.focused_cell.or_else(|| self.first_selected_cell());
+        let focused = match start {
+            Some((row, column)) => (
+                row.saturating_add_signed(row_offset).min(total_rows - 1),
+                column
+                    .saturating_add_signed(column_offset)
+                    .min(total_columns - 1),
+            ),
+            // Nothing was selected before, start from the first cell
+            None => (0, 0),
+        };
+
+        if extend {
+            let anchor = *self
+                .selection_anchor
+                .get_or_insert(start.unwrap_or(focused));
+            self.select_cells(anchor, focused);
+        } else {
+            self.selection_anchor = None;
+            self.select_cells(focused, focused);
+        }
+
+        self.focused_cell = Some(focused);
+        self.scroll_to_row = Some((focused.0, None));
+    }
+
+    /// Selects every cell between the two given cells and unselects the rest
+    fn select_cells(&mut self, from: (usize, usize), to: (usize, usize)) {
+        let rows = from.0.min(to.0)..=from.0.max(to.0);
+        let columns = self.visible_columns();
+        let selected_columns = &columns[from.1.min(to.1)..=from.1.max(to.1)];
+
+        self.table.unselect_all();
+        self.table.modify_shown_row(|formatted_rows, _| {
+            for (index, row) in formatted_rows.iter_mut().enumerate() {
+                row.selected_columns.clear();
+                if rows.contains(&index) {
+                    row.selected_columns
+                        .extend(selected_columns.iter().copied());
+                }
+            }
+        });
+    }
+
+    /// Clears the selection made with the arrow keys
+    fn clear_keyboard_selection(&mut self) {
+        if self.focused_cell.take().is_none() {
+            return;
+        }
+        self.selection_anchor = None;
+        self.table.modify_shown_row(|formatted_rows, _| {
+            for row in formatted_rows {
+                row.selected_columns.clear();
+            }
+        });
+    }
+
+    /// Formats the selected cells as aligned text, the same way the table copies them
+    fn selected_cells_text(&self) -> String {
+        let rows: Vec<&SelectableRow<UserRowData, ColumnName>> = self
+            .table
+            .get_displayed_rows()
+            .iter()
+            .filter(|row| !row.selected_columns.is_empty())
+            .collect();
+
+        let columns: Vec<ColumnName> = self
+            .visible_columns()
+            .into_iter()
+            .filter(|column| rows.iter().any(|row| row.selected_columns.contains(column)))
+            .collect();
+
+        let widths: Vec<usize> = columns
+            .iter()
+            .map(|column| {
+                rows.iter()
+                    .filter(|row| row.selected_columns.contains(column))
+                    .map(|row| column.column_text(&row.row_data).len())
+                    .max()
+                    .unwrap_or_default()
+            })
+            .collect();
+
+        let mut text = String::new();
+        for row in rows {
+            for (column, width) in columns.iter().zip(&widths) {
+                let cell = if row.selected_columns.contains(column) {
+                    column.column_text(&row.row_data)
+                } else {
+                    String::new()
+                };
+                text.push_str(&format!("{cell:<width$}", width = width + 1));
+            }
+            text.push('\n');
+        }
+        text
+    }
+
+    /// Index of the first displayed row with at least 1 column selected
+    fn first_selected_row(&mut self) -> Option<usize> {
+        let first_selected = self.table.get_selected_rows().into_iter().next()?;
+        self.table
+            .get_displayed_rows()
+            .iter()
+            .position(|row| row.id == first_selected.id)
+    }
+
+    pub fn reload_count(&self) -> u32 {
         self.reload_count
     }
     pub fn reset_reload_count(&mut self) {
@@ -327,11 +999,7 @@ impl UserTableData {
 
             if let Chat::User(user) = chat_data {
                 // As per grammers lib doc, empty name can be given if it's a deleted account
-                full_name = if user.full_name().is_empty() {
-                    "Deleted Account".to_string()
-                } else {
-                    user.full_name()
-                };
+                full_name = display_name(&user.full_name());
 
                 username = if let Some(name) = user.username() {
                     name.to_string()
@@ -339,11 +1007,7 @@ impl UserTableData {
                     "Empty".to_string()
                 };
             } else {
-                full_name = if chat_data.name().is_empty() {
-                    "Deleted Account".to_string()
-                } else {
-                    chat_data.name().to_string()
-                };
+                full_name = display_name(chat_data.name());
 
                 username = if let Some(name) = chat_data.username() {
                     name.to_string()
@@ -353,7 +1017,7 @@ impl UserTableData {
             }
         } else {
             // If there is no Chat value then it could be an anonymous user
-            full_name = "Anonymous/Unknown".to_string();
+            full_name = ANONYMOUS_LABEL.to_string();
             username = "Empty".to_string();
         }
 
@@ -375,8 +1039,10 @@ impl UserTableData {
         message: &Message,
         date: NaiveDate,
         datetime: NaiveDateTime,
+        char_config: CharCountConfig,
     ) {
         self.reload_count += 1;
+        self.char_config = char_config;
         // If a user sends multiple messages in a day, that specific day data needs to be updated
         let target_data = self.user_data.get_mut(&date).unwrap();
         let user_row_data = target_data.get_mut(&user_id).unwrap();
@@ -394,12 +1060,51 @@ impl UserTableData {
 
         self.date_nav.handler().update_dates(date);
 
-        let total_char = message_text.len() as u32;
+        let char_counts = CharCounts::from_text(message_text);
         let total_word = message_text.split_whitespace().count() as u32;
 
         user_row_data.increment_total_message();
         user_row_data.increment_total_word(total_word);
-        user_row_data.increment_total_char(total_char);
+        user_row_data.increment_char_counts(char_counts, char_config);
+
+        // GIFs are also videos, they are only counted as stickers
+        match message.media() {
+            _ if is_sticker_or_gif(message) => user_row_data.increment_sticker(),
+            Some(Media::Photo(_)) => user_row_data.increment_media(1, 0, 0),
+            Some(Media::Document(document)) => {
+                let is_video = document
+                    .mime_type()
+                    .is_some_and(|mime| mime.starts_with("video/"));
+                if is_video {
+                    user_row_data.increment_media(0, 1, 0);
+                } else {
+                    user_row_data.increment_media(0, 0, 1);
+                }
+            }
+            _ => {}
+        }
+
+        if message.edit_date().is_some() {
+            user_row_data.increment_edited();
+        }
+
+        // Only the hashes of the last few texts of each user are kept to bound the memory use so
+        // a repeat that is further apart is not detected
+        if !message_text.is_empty() {
+            let mut hasher = DefaultHasher::new();
+            message_text.hash(&mut hasher);
+            let text_hash = hasher.finish();
+
+            let recent_texts = self.recent_texts.entry(user_id).or_default();
+            if recent_texts.contains(&text_hash) {
+                user_row_data.increment_duplicate();
+            } else {
+                if recent_texts.len() == DUPLICATE_HISTORY {
+                    recent_texts.pop_front();
+                }
+                recent_texts.push_back(text_hash);
+            }
+        }
     }
 
     pub fn get_total_user(&self) -> usize {
@@ -410,14 +1115,172 @@ impl UserTableData {
         self.table.total_rows()
     }
 
+    /// IDs of every user that was counted in this chat
+    pub fn user_ids(&self) -> impl Iterator<Item = &i64> {
+        self.user_data.values().flat_map(HashMap::keys)
+    }
+
+    /// The first and the last date where at least one message was counted
+    pub fn date_range(&self) -> Option<(NaiveDate, NaiveDate)> {
+        let first = self.user_data.keys().min()?;
+        let last = self.user_data.keys().max()?;
+        Some((*first, *last))
+    }
+
+    /// Name and total message count of each user across all counted dates
+    fn user_totals(&self) -> HashMap<i64, (&String, u32)> {
+        let mut user_messages: HashMap<i64, (&String, u32)> = HashMap::new();
+
+        for rows in self.user_data.values() {
+            for (id, row) in rows {
+                let entry = user_messages.entry(*id).or_insert((&row.name, 0));
+                entry.1 += row.total_message;
+            }
+        }
+        user_messages
+    }
+
+    /// Users with the most messages across all counted dates with their total message count
+    pub fn top_users(&self, limit: usize) -> Vec<(String, u32)> {
+        let mut users: Vec<(String, u32)> = self
+            .user_totals()
+            .into_values()
+            .map(|(name, total)| (name.to_string(), total))
+            .collect();
+        users.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+        users.truncate(limit);
+        users
+    }
+
+    /// Sums, averages and date ranges of the displayed rows for each column that has one, with
+    /// the hover text explaining the value
+    fn column_totals(&self) -> HashMap<ColumnName, (String, String)> {
+        let rows = self.table.get_displayed_rows();
+        let mut totals = HashMap::new();
+
+        if rows.is_empty() {
+            return totals;
+        }
+
+        let sum = |value: fn(&UserRowData) -> u32| -> u64 {
+            rows.iter().map(|row| u64::from(value(&row.row_data))).sum()
+        };
+
+        let total_message = sum(|row| row.total_message);
+        let total_word = sum(|row| row.total_word);
+        let total_char = sum(|row| row.total_char);
+
+        let sum_columns = [
+            (ColumnName::TotalMessage, total_message),
+            (ColumnName::TotalWord, total_word),
+            (ColumnName::TotalChar, total_char),
+            (ColumnName::PhotoCount, sum(|row| row.photo_count)),
+            (ColumnName::VideoCount, sum(|row| row.video_count)),
+            (ColumnName::FileCount, sum(|row| row.file_count)),
+            (ColumnName::StickerCount, sum(|row| row.sticker_count)),
+            (ColumnName::Edited, sum(|row| row.edited_count)),
+            (ColumnName::Duplicate, sum(|row| row.duplicate_count)),
+        ];
+
+        for (column, total) in sum_columns {
+            totals.insert(
+                column,
+                (
+                    format!("Σ {total}"),
+                    format!("Sum of the {column} column of the displayed users"),
+                ),
+            );
+        }
+
+        // Averages are taken over all messages so that inactive users do not skew them
+        let total_message = total_message.max(1) as f64;
+        totals.insert(
+            ColumnName::AverageWord,
+            (
+                format!("x̄ {:.1}", total_word as f64 / total_message),
+                "Average words per message of the displayed users".to_string(),
+            ),
+        );
+        totals.insert(
+            ColumnName::AverageChar,
+            (
+                format!("x̄ {:.1}", total_char as f64 / total_message),
+                "Average characters per message of the displayed users".to_string(),
+            ),
+        );
+
+        totals.insert(
+            ColumnName::Name,
+            (
+                format!("{} users", rows.len()),
+                "Number of displayed users".to_string(),
+            ),
+        );
+
+        let whitelisted = rows.iter().filter(|row| row.row_data.whitelisted).count();
+        totals.insert(
+            ColumnName::Whitelisted,
+            (
+                format!("Σ {whitelisted}"),
+                "Whitelisted users among the displayed users".to_string(),
+            ),
+        );
+
+        let admins = rows.iter().filter(|row| row.row_data.is_admin).count();
+        totals.insert(
+            ColumnName::Admin,
+            (
+                format!("Σ {admins}"),
+                "Admins among the displayed users".to_string(),
+            ),
+        );
+
+        if let Some(first_seen) = rows.iter().map(|row| row.row_data.first_seen).min() {
+            totals.insert(
+                ColumnName::FirstMessageSeen,
+                (
+                    self.date_format.format_datetime(&first_seen),
+                    "The earliest first message of the displayed users".to_string(),
+                ),
+            );
+        }
+
+        if let Some(last_seen) = rows.iter().map(|row| row.row_data.last_seen).max() {
+            totals.insert(
+                ColumnName::LastMessageSeen,
+                (
+                    self.date_format.format_datetime(&last_seen),
+                    "The latest last message of the displayed users".to_string(),
+                ),
+            );
+        }
+
+        totals
+    }
+
     /// Recreate the rows that will be shown in the UI. Used only when date picker date is updated
     pub fn create_rows(&mut self) {
+        if self.compact_dates {
+            self.compact_old_dates();
+        }
+
+        let char_config = self.char_config;
         let mut id_map = HashMap::new();
         self.table.clear_all_rows();
         let mut total_message = 0;
         let mut whitelisted_user = HashSet::new();
         let mut whitelisted_message = 0;
 
+        // Activity is only calculated while the column is visible to keep this fast
+        let track_activity = !self.hidden_columns.contains(&ColumnName::Activity);
+        let (from, to) = {
+            let handler = self.date_nav.handler();
+            (handler.from, handler.to)
+        };
+        let total_days = ((to - from).num_days() + 1).max(1) as usize;
+        let total_points = total_days.min(SPARKLINE_POINTS);
+        let mut activity_points: HashMap<i64, Vec<u32>> = HashMap::new();
+
         // Go by all the data that are within the range and join them together
         for (date, data) in &self.user_data {
             if !self.date_nav.handler().within_range(*date) {
@@ -425,7 +1288,18 @@ impl UserTableData {
             }
 
             for (id, row) in data {
+                if self.is_filtered_out(id, row) {
+                    continue;
+                }
+
                 total_message += row.total_message;
+                if track_activity {
+                    let day = (*date - from).num_days().max(0) as usize;
+                    let point = (day * total_points / total_days).min(total_points - 1);
+                    activity_points
+                        .entry(*id)
+                        .or_insert_with(|| vec![0; total_points])[point] += row.total_message;
+                }
                 if row.whitelisted {
                     whitelisted_user.insert(row.id);
                     whitelisted_message += row.total_message;
@@ -434,22 +1308,7 @@ impl UserTableData {
                 if let Some(row_id) = id_map.get(id) {
                     self.table.add_modify_row(|rows| {
                         let target_row = rows.get_mut(row_id).unwrap();
-                        let user_row_data = &mut target_row.row_data;
-                        if user_row_data.first_seen > row.first_seen {
-                            user_row_data.set_first_seen(row.first_seen);
-                        }
-
-                        if user_row_data.last_seen < row.last_seen {
-                            user_row_data.set_last_seen(row.last_seen);
-                        }
-
-                        let total_char = row.total_char;
-                        let total_word = row.total_word;
-                        let total_message = row.total_message;
-
-                        user_row_data.increase_message_by(total_message);
-                        user_row_data.increment_total_word(total_word);
-                        user_row_data.increment_total_char(total_char);
+                        target_row.row_data.merge_row(row, char_config);
                         None
                     });
                 } else {
@@ -461,59 +1320,369 @@ impl UserTableData {
         self.total_whitelisted_message = whitelisted_message;
         self.total_message = total_message;
         self.total_whitelisted_user = whitelisted_user.len() as u32;
+
+        // Nothing is considered new on the first time the rows are created
+        let now = Instant::now();
+        let current_ids: HashSet<i64> = id_map.keys().copied().collect();
+        if !self.previous_ids.is_empty() {
+            for id in current_ids.difference(&self.previous_ids) {
+                self.added_at.insert(*id, now);
+            }
+        }
+        self.added_at
+            .retain(|_, added_at| added_at.elapsed() < NEW_ROW_HIGHLIGHT);
+        self.previous_ids = current_ids;
+
+        // Recreating the rows clears the selection
+        self.focused_cell = None;
+        self.selection_anchor = None;
+
+        // Calculate how active each user is compared to the most active user and their share of
+        // all displayed messages
+        let sort_columns: Arc<[(ColumnName, SortOrder)]> = self.sort_columns.as_slice().into();
+        let date_format = self.date_format;
+        let admins = &self.admins;
+        self.table.add_modify_row(|rows| {
+            let max_message = rows
+                .values()
+                .map(|row| row.row_data.total_message)
+                .max()
+                .unwrap_or_default();
+
+            for row in rows.values_mut() {
+                row.row_data.added_at = self.added_at.get(&row.row_data.id).copied();
+                row.row_data.sort_columns = sort_columns.clone();
+                row.row_data.date_format = date_format;
+                row.row_data.is_admin = admins.contains(&row.row_data.id);
+                row.row_data.activity_points =
+                    activity_points.remove(&row.row_data.id).unwrap_or_default();
+                row.row_data.activity = if max_message == 0 {
+                    0.0
+                } else {
+                    row.row_data.total_message as f32 / max_message as f32
+                };
+                row.row_data.message_share = if total_message == 0 {
+                    0.0
+                } else {
+                    row.row_data.total_message as f32 * 100.0 / total_message as f32
+                };
+            }
+            None
+        });
         self.table.recreate_rows();
     }
 
-    /// Mark a row as whitelisted if exists
-    pub fn set_as_whitelisted(&mut self, user_id: &[i64]) {
-        for (_d, row_data) in self.user_data.iter_mut() {
-            for (id, row) in row_data.iter_mut() {
-                for u_id in user_id {
-                    if id == u_id {
-                        row.whitelisted = true;
-                    }
+    /// Add all the user data of another chat to this table
+    pub fn merge_from(&mut self, other: &UserTableData) {
+        self.char_config = other.char_config;
+        self.admins.extend(&other.admins);
+        for (date, rows) in &other.user_data {
+            let target_data = self.user_data.entry(*date).or_default();
+            for (id, row) in rows {
+                if let Some(existing_row) = target_data.get_mut(id) {
+                    existing_row.merge_row(row, self.char_config);
+                } else {
+                    target_data.insert(*id, row.clone());
                 }
             }
         }
+        self.date_nav
+            .handler()
+            .merge_dates(other.date_nav.handler_i());
         self.create_rows();
     }
 
-    pub fn remove_blacklisted_rows(&mut self, user_id: &[i64]) {
+    /// Recalculate the char count of all rows with the given config
+    pub fn set_char_config(&mut self, config: CharCountConfig) {
+        self.char_config = config;
         for (_d, row_data) in self.user_data.iter_mut() {
-            for id in user_id {
-                row_data.remove(id);
+            for (_id, row) in row_data.iter_mut() {
+                row.set_char_config(config);
             }
         }
         self.create_rows();
     }
 
-    /// Remove whitelist status from a row if exists
-    pub fn remove_whitelist(&mut self, user_id: &[i64]) {
-        for (_d, row_data) in self.user_data.iter_mut() {
-            for (id, row) in row_data.iter_mut() {
-                for u_id in user_id {
-                    if id == u_id {
-                        row.whitelisted = false;
-                    }
+    /// Sort by the clicked column. A plain click sorts only by the column or changes its direction
+    /// if it is the primary column. With Shift, the column is added as a tiebreaker, its
+    /// direction is changed or it is removed if it was already descending
+    fn change_sort(&mut self, column: ColumnName, add_tiebreaker: bool) {
+        let position = self.sort_columns.iter().position(|(c, _)| *c == column);
+
+        match position {
+            Some(0) => {
+                let order = &mut self.sort_columns[0].1;
+                *order = match order {
+                    SortOrder::Ascending => SortOrder::Descending,
+                    SortOrder::Descending => SortOrder::Ascending,
+                };
+            }
+            Some(index) if add_tiebreaker => {
+                if matches!(self.sort_columns[index].1, SortOrder::Ascending) {
+                    self.sort_columns[index].1 = SortOrder::Descending;
+                } else {
+                    self.sort_columns.remove(index);
                 }
             }
+            None if add_tiebreaker => self.sort_columns.push((column, SortOrder::Ascending)),
+            _ => self.sort_columns = vec![(column, SortOrder::Ascending)],
         }
+
+        self.table
+            .config
+            .sort_columns
+            .clone_from(&self.sort_columns);
         self.create_rows();
     }
 
-    fn export_data(&mut self, chat_name: &str) {
-        info!("Starting exporting table data");
-        let rows = self.table.get_displayed_rows();
-        export_table_data(rows, chat_name);
+    /// Change how the dates of the rows are shown
+    pub fn set_date_format(&mut self, date_format: DateFormat) {
+        if self.date_format != date_format {
+            self.date_format = date_format;
+            self.create_rows();
+        }
     }
-}
 
-impl MainWindow {
-    pub fn show_user_table_ui(&mut self, ui: &mut Ui) {
-        let date_enabled = !self.is_processing && !self.table_i().user_data.is_empty();
+    /// Enable or disable merging the old dates into monthly buckets
+    pub fn set_compact_dates(&mut self, compact_dates: bool) {
+        if self.compact_dates != compact_dates {
+            self.compact_dates = compact_dates;
+            self.create_rows();
+        }
+    }
 
-        let (values, len) = {
-            let names = self.counter.get_chat_list();
+    /// Merges the rows of every date older than the newest `MAX_DAILY_DATES` dates into the first
+    /// day of its month. The merged dates can no longer be filtered individually
+    fn compact_old_dates(&mut self) {
+        if self.user_data.len() <= MAX_DAILY_DATES {
+            return;
+        }
+
+        let mut dates: Vec<NaiveDate> = self.user_data.keys().copied().collect();
+        dates.sort_unstable();
+        let cutoff = dates[dates.len() - MAX_DAILY_DATES];
+
+        for date in dates {
+            if date >= cutoff {
+                break;
+            }
+
+            let month_start = date.with_day(1).unwrap();
+            if month_start == date {
+                continue;
+            }
+
+            let rows = self.user_data.remove(&date).unwrap();
+            let target_data = self.user_data.entry(month_start).or_default();
+            for (id, row) in rows {
+                if let Some(existing_row) = target_data.get_mut(&id) {
+                    existing_row.merge_row(&row, self.char_config);
+                } else {
+                    target_data.insert(id, row);
+                }
+            }
+            self.dates_compacted = true;
+        }
+    }
+
+    /// Exclude or include the rows of bot users in the table
+    pub fn set_skip_bots(&mut self, skip_bots: bool) {
+        if self.skip_bots != skip_bots {
+            self.skip_bots = skip_bots;
+            self.create_rows();
+        }
+    }
+
+    /// Exclude or include the rows of the admins of the chat in the table
+    pub fn set_skip_admins(&mut self, skip_admins: bool) {
+        if self.skip_admins != skip_admins {
+            self.skip_admins = skip_admins;
+            self.create_rows();
+        }
+    }
+
+    /// Marks the rows of the given users as the admins of the chat
+    pub fn set_admins(&mut self, admins: HashSet<i64>) {
+        self.admins = admins;
+        self.create_rows();
+    }
+
+    /// Whether the row is excluded by the bot, admin or member filter
+    fn is_filtered_out(&self, id: &i64, row: &UserRowData) -> bool {
+        (self.skip_bots && row.is_bot)
+            || (self.skip_admins && self.admins.contains(id))
+            || self
+                .members
+                .as_ref()
+                .is_some_and(|members| !members.contains(id))
+    }
+
+    /// Show only the rows of the given members or all rows if `None`
+    pub fn set_members(&mut self, members: Option<HashSet<i64>>) {
+        self.members = members;
+        self.create_rows();
+    }
+
+    /// Whether the rows are filtered to the current members of the chat
+    pub fn members_only(&self) -> bool {
+        self.members.is_some()
+    }
+
+    /// Mark a row as whitelisted if exists without recreating the rows
+    pub fn mark_as_whitelisted(&mut self, user_id: &[i64]) {
+        for (_d, row_data) in self.user_data.iter_mut() {
+            for (id, row) in row_data.iter_mut() {
+                for u_id in user_id {
+                    if id == u_id {
+                        row.whitelisted = true;
+                    }
+                }
+            }
+        }
+    }
+
+    /// Mark a row as whitelisted if exists
+    pub fn set_as_whitelisted(&mut self, user_id: &[i64]) {
+        self.mark_as_whitelisted(user_id);
+        self.create_rows();
+    }
+
+    /// Removes the rows of the given users and returns the removed rows with their date
+    pub fn remove_blacklisted_rows(&mut self, user_id: &[i64]) -> Vec<(NaiveDate, UserRowData)> {
+        let mut removed = Vec::new();
+        for (date, row_data) in self.user_data.iter_mut() {
+            for id in user_id {
+                if let Some(row) = row_data.remove(id) {
+                    removed.push((*date, row));
+                }
+            }
+        }
+        self.create_rows();
+        removed
+    }
+
+    /// Adds back the rows that were removed by blacklisting
+    pub fn restore_rows(&mut self, rows: Vec<(NaiveDate, UserRowData)>) {
+        for (date, row) in rows {
+            self.user_data.entry(date).or_default().insert(row.id, row);
+        }
+        self.create_rows();
+    }
+
+    /// Remove whitelist status from a row if exists
+    pub fn remove_whitelist(&mut self, user_id: &[i64]) {
+        for (_d, row_data) in self.user_data.iter_mut() {
+            for (id, row) in row_data.iter_mut() {
+                for u_id in user_id {
+                    if id == u_id {
+                        row.whitelisted = false;
+                    }
+                }
+            }
+        }
+        self.create_rows();
+    }
+
+    /// Fill the table with rows imported from an exported CSV. The per-date data is not part of
+    /// the export so each row is placed on the date the user was last seen. Returns the counts
+    /// of the imported rows
+    pub fn import_rows(
+        &mut self,
+        rows: Vec<UserRowData>,
+        char_config: CharCountConfig,
+    ) -> CounterCounts {
+        let mut counts = CounterCounts::default();
+        self.char_config = char_config;
+        for mut row in rows {
+            counts.total_message += row.total_message as i32;
+            counts.total_word += u64::from(row.total_word);
+            if row.whitelisted {
+                counts.add_whitelisted_user(row.id);
+                counts.whitelisted_message += row.total_message as i32;
+            }
+
+            row.char_counts = CharCounts::uniform(row.total_char);
+            counts.char_counts.add(row.char_counts);
+            row.set_char_config(char_config);
+
+            let date = row.last_seen.date();
+            self.date_nav.handler().update_dates(date);
+
+            let target_data = self.user_data.entry(date).or_default();
+            if let Some(existing_row) = target_data.get_mut(&row.id) {
+                existing_row.merge_row(&row, char_config);
+            } else {
+                target_data.insert(row.id, row);
+            }
+        }
+        self.create_rows();
+
+        counts.set_total_user(self.get_total_users_full() as i32);
+        counts
+    }
+
+    pub fn export_data(&mut self, chat_name: &str, delimiter: CsvDelimiter, anonymize: bool) {
+        info!("Starting exporting table data");
+        let mut rows: Vec<UserRowData> = self
+            .table
+            .get_displayed_rows()
+            .iter()
+            .map(|row| row.row_data.clone())
+            .collect();
+
+        if anonymize {
+            anonymize_rows(&mut rows);
+        }
+        export_table_data(&rows, chat_name, delimiter);
+    }
+
+    /// Totals of each date within the selected date range, oldest first. Skipped bots, admins and
+    /// non-members are left out the same way as in the table
+    fn date_totals(&self) -> Vec<DateTotals> {
+        let mut dates: Vec<&NaiveDate> = self
+            .user_data
+            .keys()
+            .filter(|date| self.date_nav.handler_i().within_range(**date))
+            .collect();
+        dates.sort_unstable();
+
+        dates
+            .into_iter()
+            .map(|date| {
+                let mut totals = DateTotals {
+                    date: *date,
+                    total_message: 0,
+                    unique_users: 0,
+                    whitelisted_message: 0,
+                };
+                for (id, row) in &self.user_data[date] {
+                    if self.is_filtered_out(id, row) {
+                        continue;
+                    }
+                    totals.total_message += row.total_message;
+                    totals.unique_users += 1;
+                    if row.whitelisted {
+                        totals.whitelisted_message += row.total_message;
+                    }
+                }
+                totals
+            })
+            .collect()
+    }
+
+    pub fn export_date_totals(&self, chat_name: &str, delimiter: CsvDelimiter) {
+        info!("Starting exporting date totals");
+        let rows = self.date_totals();
+        export_date_totals(&rows, chat_name, delimiter);
+    }
+}
+
+impl MainWindow {
+    pub fn show_user_table_ui(&mut self, ui: &mut Ui) {
+        let date_enabled = !self.is_processing && !self.table_i().user_data.is_empty();
+
+        let (values, len) = {
+            let names = self.counter.get_chat_list();
 
             if names.is_empty() {
                 (vec!["No chat available".to_string()], 0)
@@ -530,6 +1699,10 @@ impl MainWindow {
                 len,
                 |i| &values[i],
             );
+            if len > 0 {
+                let chat_name = self.counter.selected_chat_name(self.table_chat_index);
+                self.show_chat_note(ui, &chat_name);
+            }
             ui.separator();
             let button = Button::new("Export Table Data");
             if ui
@@ -538,13 +1711,192 @@ impl MainWindow {
                 .clicked()
             {
                 let chat_name = self.counter.selected_chat_name(self.table_chat_index);
-                self.table().export_data(&chat_name);
+                let delimiter = self.settings.csv_delimiter;
+                let anonymize = self.settings.anonymize_export;
+                self.table().export_data(&chat_name, delimiter, anonymize);
+                self.process_state =
+                    ProcessState::DataExported(current_dir().unwrap().to_string_lossy().into(), 1);
+            };
+
+            let overlap_enabled = !self.is_processing && self.counter.total_chats() > 1;
+            let button = Button::new("Chat Overlap");
+            if ui
+                .add_enabled(overlap_enabled, button)
+                .on_hover_text("Show the users that were found in 2 or more of the counted chats")
+                .clicked()
+            {
+                self.find_overlap_users();
+            };
+
+            let button = Button::new("Export Dates");
+            if ui
+                .add_enabled(date_enabled, button)
+                .on_hover_text("Export the total messages, unique users and whitelisted messages of each date in the selected date range in CSV format")
+                .clicked()
+            {
+                let chat_name = self.counter.selected_chat_name(self.table_chat_index);
+                let delimiter = self.settings.csv_delimiter;
+                self.table().export_date_totals(&chat_name, delimiter);
                 self.process_state =
-                    ProcessState::DataExported(current_dir().unwrap().to_string_lossy().into());
+                    ProcessState::DataExported(current_dir().unwrap().to_string_lossy().into(), 1);
+            };
+
+            let export_all_enabled = !self.is_processing && len > 0;
+            let button = Button::new("Export All");
+            if ui
+                .add_enabled(export_all_enabled, button)
+                .on_hover_text(
+                    "Export Table data of all counted chats in CSV format, one file per chat",
+                )
+                .clicked()
+            {
+                self.export_all_tables();
             };
+
+            let recount_enabled = !self.is_processing
+                && len > 0
+                && self
+                    .counter
+                    .can_recount(&self.counter.selected_chat_name(self.table_chat_index));
+            let button = Button::new("Recount");
+            if ui
+                .add_enabled(recount_enabled, button)
+                .on_hover_text(
+                    "Clear the data of this chat and count it again with the same message range",
+                )
+                .clicked()
+            {
+                let chat_name = self.counter.selected_chat_name(self.table_chat_index);
+                self.recount_chat(&chat_name);
+            };
+
+            if self.table_i().members_only() {
+                if ui
+                    .add_enabled(!self.is_processing, Button::new("Show Left Users"))
+                    .on_hover_text("Show the users that are no longer members of the chat again")
+                    .clicked()
+                {
+                    self.table().set_members(None);
+                }
+            } else if ui
+                .add_enabled(recount_enabled, Button::new("Current Members Only"))
+                .on_hover_text(
+                    "Fetch the members of this chat and hide the users that left or were removed from the table",
+                )
+                .clicked()
+            {
+                self.filter_to_members();
+            }
+
+            if ui
+                .add_enabled(recount_enabled, Button::new("Fetch Admins"))
+                .on_hover_text("Fetch the admins and the owner of this chat to mark them in the Admin column and to allow skipping them")
+                .clicked()
+            {
+                self.fetch_admins();
+            }
+
+            ui.add_enabled_ui(!self.is_processing, |ui| {
+                ui.menu_button("Import Table CSV", |ui| {
+                    ui.add(
+                        TextEdit::singleline(&mut self.table_import_path)
+                            .hint_text("Path to the exported CSV file"),
+                    );
+                    let import_enabled = !self.table_import_path.trim().is_empty();
+                    if ui
+                        .add_enabled(import_enabled, Button::new("Import"))
+                        .clicked()
+                    {
+                        self.import_table();
+                        ui.close_menu();
+                    }
+                })
+                .response
+                .on_hover_text(
+                    "Load a table exported earlier as a new chat. Charts are not available for imported tables",
+                );
+            });
+
+            self.show_csv_delimiter_option(ui);
+
+            if self.is_count_only(self.table_chat_index) {
+                return;
+            }
+            ui.separator();
+
+            ui.menu_button("Columns", |ui| {
+                let mut to_toggle = None;
+                let table = self.table();
+                for column in ColumnName::iter() {
+                    let mut visible = !table.hidden_columns.contains(&column);
+                    // Do not allow hiding the last visible column
+                    let enabled =
+                        !visible || table.hidden_columns.len() + 1 < ColumnName::iter().len();
+                    if ui
+                        .add_enabled(enabled, Checkbox::new(&mut visible, column.to_string()))
+                        .changed()
+                    {
+                        to_toggle = Some(column);
+                    }
+                }
+                if let Some(column) = to_toggle {
+                    table.toggle_column(column);
+                }
+            })
+            .response
+            .on_hover_text("Show or hide table columns");
+
+            ui.separator();
+            self.show_char_count_options(ui);
+
+            ui.separator();
+            let table = self.table();
+            ui.checkbox(&mut table.table.config.activity_colors, "Activity Colors")
+                .on_hover_text("Tint each row based on the number of messages compared to the most active user");
+
+            ui.separator();
+            if ui
+                .checkbox(&mut self.settings.skip_bots, "Skip Bots")
+                .on_hover_text("Exclude bot users from the tables and charts of all chats without blacklisting them")
+                .changed()
+            {
+                self.apply_display_settings();
+                save_settings(&self.settings);
+            }
+
+            ui.separator();
+            if ui
+                .checkbox(&mut self.settings.skip_admins, "Skip Admins")
+                .on_hover_text("Exclude the admins from the tables and charts of the chats where the admins were fetched without blacklisting them")
+                .changed()
+            {
+                self.apply_display_settings();
+                save_settings(&self.settings);
+            }
+
+            ui.separator();
+            if ui
+                .checkbox(&mut self.settings.compact_dates, "Compact Old Dates")
+                .on_hover_text(format!("Limit memory usage on very long counts by merging the data of dates older than the newest {MAX_DAILY_DATES} dates into one entry per month
+
+Warning: Date navigation becomes coarser for the merged dates and they cannot be split again"))
+                .changed()
+            {
+                self.apply_display_settings();
+                save_settings(&self.settings);
+            }
         });
         ui.separator();
 
+        if self.overlap_users.is_some() {
+            self.show_overlap_modal(ui);
+        }
+
+        if self.is_count_only(self.table_chat_index) {
+            ui.label("Disabled for this count. The chat was counted without per-user stats");
+            return;
+        }
+
         ui.horizontal(|ui| {
             ui.label(format!("Total User: {}", self.table_i().get_total_user()));
             ui.separator();
@@ -559,9 +1911,48 @@ impl MainWindow {
                 "Whitelisted Message: {}",
                 self.table_i().total_whitelisted_message
             ));
+            ui.separator();
+
+            let has_rows = self.table_i().get_total_user() > 0;
+            if ui
+                .add_enabled(has_rows, Button::new("Scroll to Top"))
+                .on_hover_text("Jump to the first row of the table")
+                .clicked()
+            {
+                self.table().scroll_to_row = Some((0, Some(Align::TOP)));
+            }
+
+            if ui
+                .add_enabled(has_rows, Button::new("Scroll to Selected"))
+                .on_hover_text("Jump to the first row with at least 1 column selected")
+                .clicked()
+            {
+                let table = self.table();
+                table.scroll_to_row = table
+                    .first_selected_row()
+                    .map(|row| (row, Some(Align::TOP)));
+            }
+
+            ui.separator();
+            let whitelist_enabled = has_rows && !self.is_processing;
+            if ui
+                .add_enabled(whitelist_enabled, Button::new("Whitelist Displayed"))
+                .on_hover_text("Whitelist all users that are currently displayed in the table")
+                .clicked()
+            {
+                if self.table_i().get_total_user() > WHITELIST_CONFIRM_LIMIT {
+                    self.table().show_whitelist_modal = true;
+                } else {
+                    self.whitelist_displayed_rows();
+                }
+            }
         });
         ui.separator();
 
+        if self.table_i().show_whitelist_modal {
+            self.show_whitelist_modal(ui);
+        }
+
         // Date section remains disabled while data processing is ongoing or the table is empty
         ui.add_enabled_ui(date_enabled, |ui| {
             ui.horizontal(|ui| {
@@ -618,6 +2009,11 @@ impl MainWindow {
                 if ui.button(format!("Next {}", table.date_nav.nav_name())).on_hover_text(next_hover).clicked() {
                     table.date_nav.go_next();
                 };
+
+                if table.dates_compacted {
+                    ui.separator();
+                    ui.label("⚠ Old dates are grouped by month").on_hover_text(format!("Dates older than the newest {MAX_DAILY_DATES} dates were merged into the first day of their month to save memory. Date filters only apply to the whole month for these dates"));
+                }
             });
         });
 
@@ -643,11 +2039,12 @@ impl MainWindow {
 
         ui.add_space(5.0);
 
-        let mut clip_added = 0;
-
         let to_whitelist_selected = self.table().table.config.whitelist_rows;
         let to_blacklist_selected = self.table().table.config.blacklisted_rows;
         let to_copy = self.table().table.config.copy_selected;
+        let to_copy_markdown = self.table().table.config.copy_markdown;
+        let to_copy_json = self.table().table.config.copy_json;
+        let to_copy_mentions = self.table().table.config.copy_mentions;
 
         if to_whitelist_selected {
             self.table().table.config.whitelist_rows = false;
@@ -664,40 +2061,358 @@ impl MainWindow {
             self.copy_selected_cells(ui);
         }
 
-        self.table().table.show_ui(ui, |builder| {
-            let mut table = builder
-                .striped(true)
-                .resizable(true)
-                .cell_layout(Layout::left_to_right(Align::Center))
-                .drag_to_scroll(false)
-                .auto_shrink([false; 2])
-                .min_scrolled_height(0.0);
+        if to_copy_markdown {
+            self.table().table.config.copy_markdown = false;
+            self.copy_as_markdown(ui);
+        }
+
+        if to_copy_json {
+            self.table().table.config.copy_json = false;
+            self.copy_as_json(ui);
+        }
+
+        if to_copy_mentions {
+            self.table().table.config.copy_mentions = false;
+            self.copy_as_mentions(ui);
+        }
+
+        if let Some(column) = self.table().table.config.copy_column.take() {
+            self.copy_column(ui, column);
+        }
+
+        if let Some((column, add_tiebreaker)) = self.table().table.config.sort_clicked.take() {
+            self.table().change_sort(column, add_tiebreaker);
+        }
 
-            for _ in ColumnName::iter() {
-                let mut column = Column::initial(100.0);
-                if clip_added < 2 {
-                    column = column.clip(true);
-                    clip_added += 1;
+        // Move the focused cell with the arrow keys. Shift extends the selection
+        if !ui.ctx().wants_keyboard_input() {
+            let (row_offset, column_offset, extend) = ui.input(|i| {
+                let mut row_offset = 0;
+                let mut column_offset = 0;
+                if i.key_pressed(Key::ArrowUp) {
+                    row_offset -= 1;
+                }
+                if i.key_pressed(Key::ArrowDown) {
+                    row_offset += 1;
                 }
-                table = table.column(column);
+                if i.key_pressed(Key::ArrowLeft) {
+                    column_offset -= 1;
+                }
+                if i.key_pressed(Key::ArrowRight) {
+                    column_offset += 1;
+                }
+                (row_offset, column_offset, i.modifiers.shift)
+            });
+
+            if row_offset != 0 || column_offset != 0 {
+                self.table().move_focus(row_offset, column_offset, extend);
+            }
+        }
+
+        // A click on the table starts a new selection. The keyboard selection is not tracked by
+        // the table so it has to be cleared here
+        let table_rect = ui.available_rect_before_wrap();
+        let layer_id = ui.layer_id();
+        let click_pos = ui.input(|i| {
+            if i.pointer.primary_pressed() && !i.modifiers.ctrl {
+                i.pointer.interact_pos()
+            } else {
+                None
             }
-            table
+        });
+        let table_clicked = click_pos.is_some_and(|pos| {
+            table_rect.contains(pos) && ui.ctx().layer_id_at(pos) == Some(layer_id)
+        });
+        if table_clicked {
+            self.table().clear_keyboard_selection();
+        }
+
+        let copy_requested = ui.input(|i| i.events.contains(&Event::Copy));
+        let visible_columns = self.table_i().visible_columns();
+        let scroll_to_row = self.table().scroll_to_row.take();
+
+        // Leave room for the footer, the table takes all the available space otherwise
+        let table_size =
+            ui.available_size() - vec2(0.0, FOOTER_HEIGHT + ui.spacing().item_spacing.y);
+        ui.allocate_ui(table_size, |ui| {
+            self.table().table.show_ui(ui, |builder| {
+                let mut table = builder
+                    .striped(true)
+                    .resizable(true)
+                    .cell_layout(Layout::left_to_right(Align::Center))
+                    .drag_to_scroll(false)
+                    .auto_shrink([false; 2])
+                    .min_scrolled_height(0.0);
+
+                for column_name in visible_columns {
+                    let mut column = Column::initial(100.0);
+                    if matches!(column_name, ColumnName::Name | ColumnName::Username) {
+                        column = column.clip(true);
+                    }
+                    table = table.column(column);
+                }
+
+                if let Some((row, align)) = scroll_to_row {
+                    table = table.scroll_to_row(row, align);
+                }
+                table
+            })
+        });
+
+        self.show_table_footer(ui);
+
+        // The table only copies the cells it selected itself
+        if copy_requested && self.table_i().focused_cell.is_some() {
+            ui.ctx().copy_text(self.table_i().selected_cells_text());
+        }
+    }
+
+    /// Fetches the current members of the selected chat to hide the users that left
+    fn filter_to_members(&mut self) {
+        let chat_name = self.counter.selected_chat_name(self.table_chat_index);
+        self.start_chat_fetch(
+            ProcessState::FetchingMembers(chat_name.clone()),
+            ProcessStart::FetchChatMembers(chat_name),
+        );
+    }
+
+    /// Fetches the admins of the selected chat to mark them in the table and the chart
+    fn fetch_admins(&mut self) {
+        let chat_name = self.counter.selected_chat_name(self.table_chat_index);
+        self.start_chat_fetch(
+            ProcessState::FetchingAdmins(chat_name.clone()),
+            ProcessStart::FetchAdmins(chat_name),
+        );
+    }
+
+    /// Starts a fetch about the selected chat with the selected session
+    fn start_chat_fetch(&mut self, state: ProcessState, process: ProcessStart) {
+        let selected_session = self.get_selected_session();
+
+        if selected_session.is_empty() {
+            self.process_state = ProcessState::EmptySelectedSession;
+            return;
+        }
+
+        let client = self.tg_clients.get(&selected_session).unwrap().clone();
+        self.process_state = state;
+        self.is_processing = true;
+
+        self.runtime.spawn(async move {
+            client.start_process(process).await;
         });
     }
 
+    /// Shows the column totals of the displayed rows, each placed below its column header
+    fn show_table_footer(&mut self, ui: &mut Ui) {
+        let (footer_rect, _) =
+            ui.allocate_exact_size(vec2(ui.available_width(), FOOTER_HEIGHT), Sense::hover());
+        ui.painter()
+            .rect_filled(footer_rect, 0.0, ui.visuals().faint_bg_color);
+
+        let table = self.table_i();
+        let totals = table.column_totals();
+        let header_ranges = &table.table.config.header_ranges;
+
+        // Horizontally scrolled headers can be partially outside the footer
+        let clip_rect = footer_rect.intersect(ui.clip_rect());
+        for column in table.visible_columns() {
+            let (Some((text, hover_text)), Some(x_range)) =
+                (totals.get(&column), header_ranges.get(&column))
+            else {
+                continue;
+            };
+
+            let cell_rect = Rect::from_x_y_ranges(*x_range, footer_rect.y_range());
+            ui.scope_builder(
+                UiBuilder::new()
+                    .max_rect(cell_rect)
+                    .layout(Layout::left_to_right(Align::Center)),
+                |ui| {
+                    ui.set_clip_rect(clip_rect.intersect(cell_rect));
+                    ui.add(Label::new(RichText::new(text).strong()).truncate())
+                        .on_hover_text(hover_text);
+                },
+            );
+        }
+    }
+
+    /// Options for how characters are counted. Changes are applied to all tables and saved
+    fn show_char_count_options(&mut self, ui: &mut Ui) {
+        let mut config = self.settings.char_count;
+
+        ComboBox::from_id_salt("Char Count Box")
+            .selected_text(config.mode.to_string())
+            .show_ui(ui, |ui| {
+                for mode in CharCountMode::iter() {
+                    ui.selectable_value(&mut config.mode, mode, mode.to_string());
+                }
+            })
+            .response
+            .on_hover_text(
+                "How characters are counted
+
+Bytes: Length of the message in bytes. Non-Latin characters take multiple bytes
+Characters: Number of Unicode characters
+Graphemes: Number of user-perceived characters. An emoji with modifiers counts as 1",
+            );
+
+        ui.checkbox(&mut config.exclude_whitespace, "Exclude Whitespace")
+            .on_hover_text("Whether to exclude whitespace from the char count");
+
+        if config != self.settings.char_count {
+            self.settings.char_count = config;
+            save_settings(&self.settings);
+            for table in self.table_all() {
+                table.set_char_config(config);
+            }
+        }
+    }
+
+    fn show_csv_delimiter_option(&mut self, ui: &mut Ui) {
+        let mut delimiter = self.settings.csv_delimiter;
+
+        ComboBox::from_id_salt("CSV Delimiter Box")
+            .selected_text(delimiter.to_string())
+            .show_ui(ui, |ui| {
+                for value in CsvDelimiter::iter() {
+                    ui.selectable_value(&mut delimiter, value, value.to_string());
+                }
+            })
+            .response
+            .on_hover_text(
+                "The delimiter used in the exported CSV files. Use Semicolon if the spreadsheet app uses comma as the decimal separator",
+            );
+
+        if delimiter != self.settings.csv_delimiter {
+            self.settings.csv_delimiter = delimiter;
+            save_settings(&self.settings);
+        }
+
+        if ui
+            .checkbox(&mut self.settings.anonymize_export, "Anonymize")
+            .on_hover_text("Replace the names, usernames, user IDs and session names with pseudonyms in the exported CSV files")
+            .changed()
+        {
+            save_settings(&self.settings);
+        }
+    }
+
+    /// Exports the table data of every counted chat, one CSV file per chat
+    fn export_all_tables(&mut self) {
+        let chat_list = self.counter.get_chat_list();
+        let delimiter = self.settings.csv_delimiter;
+        let anonymize = self.settings.anonymize_export;
+        let mut total_exported = 0;
+
+        for (table, chat_name) in self.table_all().zip(chat_list) {
+            if table.user_data.is_empty() {
+                continue;
+            }
+            table.export_data(&chat_name, delimiter, anonymize);
+            total_exported += 1;
+        }
+
+        self.process_state = ProcessState::DataExported(
+            current_dir().unwrap().to_string_lossy().into(),
+            total_exported,
+        );
+    }
+
     fn copy_selected_cells(&mut self, ui: &mut Ui) {
-        self.table().table.copy_selected_cells(ui);
+        if self.table_i().focused_cell.is_some() {
+            ui.ctx().copy_text(self.table_i().selected_cells_text());
+        } else {
+            self.table().table.copy_selected_cells(ui);
+        }
+        self.process_state = ProcessState::DataCopied;
+    }
+
+    /// Copies the selected rows as a Markdown table. Uses all displayed rows if nothing is selected
+    fn copy_as_markdown(&mut self, ui: &mut Ui) {
+        let columns = self.table_i().visible_columns();
+        let mut rows = self.table().table.get_selected_rows();
+        if rows.is_empty() {
+            rows = self.table().table.get_displayed_rows().clone();
+        }
+
+        ui.ctx().copy_text(table_to_markdown(&rows, &columns));
+        self.process_state = ProcessState::DataCopied;
+    }
+
+    /// Copies the values of a column from all displayed rows, one value per line
+    fn copy_column(&mut self, ui: &mut Ui, column: ColumnName) {
+        let values: Vec<String> = self
+            .table()
+            .table
+            .get_displayed_rows()
+            .iter()
+            .map(|row| column.column_text(&row.row_data))
+            .collect();
+
+        ui.ctx().copy_text(values.join("\n"));
+        self.process_state = ProcessState::DataCopied;
+    }
+
+    /// Copies the selected rows as a JSON array
+    fn copy_as_json(&mut self, ui: &mut Ui) {
+        let rows: Vec<UserRowData> = self
+            .table()
+            .table
+            .get_selected_rows()
+            .into_iter()
+            .map(|row| row.row_data)
+            .collect();
+
+        match serde_json::to_string_pretty(&rows) {
+            Ok(json) => {
+                ui.ctx().copy_text(json);
+                self.process_state = ProcessState::DataCopied;
+            }
+            Err(e) => error!("Failed to serialize the selected rows. Error: {e}"),
+        }
+    }
+
+    /// Copies the selected users as Telegram mentions, one per line
+    fn copy_as_mentions(&mut self, ui: &mut Ui) {
+        let mentions: Vec<String> = self
+            .table()
+            .table
+            .get_selected_rows()
+            .iter()
+            // Anonymous rows do not belong to a user that can be mentioned
+            .filter(|row| row.row_data.name != ANONYMOUS_LABEL)
+            .map(|row| user_mention(&row.row_data.name, row.row_data.id))
+            .collect();
+
+        if mentions.is_empty() {
+            return;
+        }
+
+        ui.ctx().copy_text(mentions.join("\n"));
         self.process_state = ProcessState::DataCopied;
     }
 
     /// Marks all the rows with at least 1 column selected as whitelisted
     fn whitelist_selected_rows(&mut self) {
         let table_selected_rows = self.table().table.get_selected_rows();
+        self.whitelist_rows(&table_selected_rows);
+    }
+
+    /// Marks all the rows that are currently displayed in the table as whitelisted
+    fn whitelist_displayed_rows(&mut self) {
+        let displayed_rows = self.table().table.get_displayed_rows().clone();
+        self.whitelist_rows(&displayed_rows);
+    }
+
+    /// Whitelists the given rows except the anonymous and imported ones
+    fn whitelist_rows(&mut self, rows: &[SelectableRow<UserRowData, ColumnName>]) {
         let mut selected_rows = Vec::new();
 
-        for selected in &table_selected_rows {
+        for selected in rows {
             let row_data = &selected.row_data;
-            if row_data.name != "Anonymous/Unknown" {
+            // Imported rows have no chat to save the user with
+            if row_data.name != ANONYMOUS_LABEL && row_data.belongs_to.is_some() {
                 selected_rows.push(row_data);
             }
         }
@@ -706,24 +2421,166 @@ impl MainWindow {
         let mut packed_chats = Vec::new();
 
         let mut all_ids = Vec::new();
+        let mut new_ids = Vec::new();
         for row in selected_rows {
             let cloned_row = row.clone();
             all_ids.push(row.id);
+            if !self.whitelist.is_user_whitelisted(row.id) {
+                new_ids.push(row.id);
+            }
             self.whitelist.add_to_whitelist(
                 row.name.clone(),
                 row.username.clone(),
                 row.id,
                 row.belongs_to.clone().unwrap(),
-                row.seen_by.clone(),
+                row.primary_session(),
             );
             let hex_value = cloned_row.belongs_to.unwrap().pack().to_hex();
-            packed_chats.push(PackedWhitelistedUser::new(hex_value, cloned_row.seen_by));
+            packed_chats.push(PackedWhitelistedUser::new(hex_value, row.primary_session()));
         }
         self.table().set_as_whitelisted(&all_ids);
         self.chart().reset_saved_bars();
 
         self.whitelist.save_whitelisted_users(false);
         self.process_state = ProcessState::UsersWhitelisted(total_to_whitelist);
+        if !new_ids.is_empty() {
+            self.last_action = Some(UndoAction::Whitelisted(new_ids));
+        }
+    }
+
+    /// Finds the users that appear in more than one counted chat, most shared users first
+    fn find_overlap_users(&mut self) {
+        let chat_list = self.counter.get_chat_list();
+        let mut users: HashMap<i64, OverlapUser> = HashMap::new();
+
+        for (table, chat_name) in self.table_all().zip(chat_list) {
+            for (id, (name, total_message)) in table.user_totals() {
+                let user = users.entry(id).or_insert_with(|| OverlapUser {
+                    name: name.to_string(),
+                    id,
+                    chats: Vec::new(),
+                });
+                user.chats.push((chat_name.clone(), total_message));
+            }
+        }
+
+        let mut overlap_users: Vec<OverlapUser> = users
+            .into_values()
+            .filter(|user| user.chats.len() > 1)
+            .collect();
+        overlap_users.sort_by(|a, b| {
+            b.chats
+                .len()
+                .cmp(&a.chats.len())
+                .then_with(|| b.total_message().cmp(&a.total_message()))
+                .then_with(|| a.name.cmp(&b.name))
+        });
+        self.overlap_users = Some(overlap_users);
+    }
+
+    /// Shows the users found in more than one chat with their message count in each chat
+    fn show_overlap_modal(&mut self, ui: &mut Ui) {
+        let Some(overlap_users) = &self.overlap_users else {
+            return;
+        };
+        let mut close = false;
+
+        let modal = Modal::new(Id::new("overlap_modal")).show(ui.ctx(), |ui| {
+            ui.set_width(500.0);
+            ui.vertical_centered(|ui| {
+                ui.heading("Users in multiple chats");
+            });
+            ui.add_space(5.0);
+
+            if overlap_users.is_empty() {
+                ui.label("No user was found in more than one of the counted chats.");
+            } else {
+                ui.label(format!(
+                    "{} users were found in 2 or more chats",
+                    overlap_users.len()
+                ));
+                ui.add_space(5.0);
+
+                ScrollArea::vertical().max_height(300.0).show(ui, |ui| {
+                    Grid::new("Overlap Grid")
+                        .num_columns(4)
+                        .striped(true)
+                        .spacing([20.0, 5.0])
+                        .show(ui, |ui| {
+                            ui.strong("Name");
+                            ui.strong("User ID");
+                            ui.strong("Chats");
+                            ui.strong("Messages");
+                            ui.end_row();
+
+                            for user in overlap_users {
+                                let messages = user
+                                    .chats
+                                    .iter()
+                                    .map(|(chat_name, count)| format!("{chat_name}: {count}"))
+                                    .collect::<Vec<String>>()
+                                    .join("\n");
+                                ui.label(&user.name);
+                                ui.label(user.id.to_string());
+                                ui.label(user.chats.len().to_string());
+                                ui.label(messages);
+                                ui.end_row();
+                            }
+                        });
+                });
+            }
+            ui.add_space(5.0);
+
+            ui.vertical_centered(|ui| {
+                if ui.button("Close").clicked() {
+                    close = true;
+                }
+            });
+        });
+
+        if close || modal.should_close() {
+            self.overlap_users = None;
+        }
+    }
+
+    /// Confirmation modal before whitelisting a large number of displayed rows
+    fn show_whitelist_modal(&mut self, ui: &mut Ui) {
+        let total_rows = self.table_i().get_total_user();
+
+        let modal = Modal::new(Id::new("whitelist_modal")).show(ui.ctx(), |ui| {
+            ui.set_width(250.0);
+            ui.vertical_centered(|ui| {
+                ui.heading("Whitelist all displayed users?");
+            });
+            ui.add_space(5.0);
+            ui.label(format!(
+                "{total_rows} users will be added to the whitelist."
+            ));
+            ui.add_space(5.0);
+
+            ui.horizontal(|ui| {
+                let button_width = ui.available_width() / 2.0;
+
+                if ui
+                    .add_sized([button_width - 5.0, 15.0], Button::new("Whitelist"))
+                    .clicked()
+                {
+                    self.table().show_whitelist_modal = false;
+                    self.whitelist_displayed_rows();
+                }
+
+                if ui
+                    .add_sized([button_width - 5.0, 15.0], Button::new("Cancel"))
+                    .clicked()
+                {
+                    self.table().show_whitelist_modal = false;
+                }
+            });
+        });
+
+        if modal.should_close() {
+            self.table().show_whitelist_modal = false;
+        }
     }
 
     /// Marks all the rows with at least 1 column selected as blacklisted
@@ -733,7 +2590,8 @@ impl MainWindow {
 
         for selected in &table_selected_rows {
             let row_data = &selected.row_data;
-            if row_data.name != "Anonymous/Unknown" {
+            // Imported rows have no chat to save the user with
+            if row_data.name != ANONYMOUS_LABEL && row_data.belongs_to.is_some() {
                 selected_rows.push(row_data);
             }
         }
@@ -755,21 +2613,103 @@ impl MainWindow {
                 row.username.clone(),
                 row.id,
                 row.belongs_to.clone().unwrap(),
-                row.seen_by.clone(),
+                row.primary_session(),
             );
             let hex_value = cloned_row.belongs_to.unwrap().pack().to_hex();
-            packed_chats.push(PackedBlacklistedUser::new(hex_value, cloned_row.seen_by));
+            packed_chats.push(PackedBlacklistedUser::new(hex_value, row.primary_session()));
         }
 
-        for chart in self.chart_all() {
-            chart.clear_blacklisted(&names);
-        }
+        let chart_users = self
+            .chart_all()
+            .map(|chart| chart.clear_blacklisted(&names))
+            .collect();
 
-        for table in self.table_all() {
-            table.remove_blacklisted_rows(&all_ids);
-        }
+        let table_rows = self
+            .table_all()
+            .map(|table| table.remove_blacklisted_rows(&all_ids))
+            .collect();
 
         self.blacklist.save_blacklisted_users(false);
         self.process_state = ProcessState::UsersBlacklisted(total_to_blacklist);
+        if !all_ids.is_empty() {
+            self.last_action = Some(UndoAction::Blacklisted {
+                ids: all_ids,
+                table_rows,
+                chart_users,
+            });
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::import_table_data;
+    use std::fs;
+
+    fn row(name: &str, id: i64, seen_by: &str) -> UserRowData {
+        UserRowData::new(
+            name,
+            "username",
+            id,
+            false,
+            None,
+            NaiveDateTime::default(),
+            seen_by.to_string(),
+        )
+    }
+
+    #[test]
+    fn anonymizes_rows() {
+        let mut rows = vec![
+            row("Alice", 10, "session_a"),
+            row("Bob", 20, "session_a, session_b"),
+            row("Alice", 10, "session_b"),
+            row(ANONYMOUS_LABEL, 30, "session_a"),
+        ];
+        anonymize_rows(&mut rows);
+
+        // The same user gets the same pseudonym and different users get different ones
+        assert_eq!(rows[0].name, rows[2].name);
+        assert_eq!(rows[0].id, rows[2].id);
+        assert_ne!(rows[0].name, rows[1].name);
+        for row in &rows[..3] {
+            assert!(row.name.starts_with("User #"));
+            assert_eq!(row.username, "Empty");
+            assert!(row.id == 1 || row.id == 2);
+        }
+
+        // Anonymous senders are left as they are
+        assert_eq!(rows[3].name, ANONYMOUS_LABEL);
+        assert_eq!(rows[3].id, 30);
+
+        assert_eq!(rows[0].seen_by, "Session #1");
+        assert_eq!(rows[1].seen_by, "Session #1, Session #2");
+        assert_eq!(rows[2].seen_by, "Session #2");
+        assert_eq!(rows[3].seen_by, "Session #1");
+    }
+
+    #[test]
+    fn imports_baseline_export() {
+        // Exports made before the media, sticker, edit and duplicate counts and the session
+        // column were added
+        let csv = "name,username,id,total_message,total_word,total_char,average_word,average_char,first_seen,last_seen,whitelisted
+Alice,alice,1,10,50,250,5,25,2024-01-01T10:00:00,2024-01-02T10:00:00,false
+Bob,Empty,2,3,6,30,2,10,2024-01-01T11:00:00,2024-01-01T12:00:00,true
+";
+        let path = std::env::temp_dir().join("Baseline Chat Table Export 2024-01-02 10-00-00.csv");
+        fs::write(&path, csv).unwrap();
+
+        let imported = import_table_data(path.to_str().unwrap());
+        fs::remove_file(&path).unwrap();
+        let (chat_name, rows) = imported.unwrap();
+
+        assert_eq!(chat_name, "Baseline Chat (Imported)");
+        assert_eq!(rows.len(), 2);
+        assert_eq!(rows[0].name, "Alice");
+        assert_eq!(rows[0].total_message, 10);
+        assert_eq!(rows[0].photo_count, 0);
+        assert!(rows[0].seen_by.is_empty());
+        assert!(rows[1].whitelisted);
     }
 }
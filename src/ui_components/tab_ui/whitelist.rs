@@ -7,12 +7,17 @@ use egui_selectable_table::{
 };
 use grammers_client::types::Chat;
 use log::{error, info};
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 
 use crate::tg_handler::ProcessStart;
-use crate::ui_components::processor::{ColumnName, PackedWhitelistedUser, ProcessState};
+use crate::ui_components::processor::{
+    ColumnName, ListedUser, PackedWhitelistedUser, ProcessState, UndoAction,
+};
 use crate::ui_components::MainWindow;
-use crate::utils::{get_whitelisted, save_whitelisted_users, separate_whitelist_by_seen};
+use crate::utils::{
+    backup_whitelisted, display_name, get_whitelisted, save_whitelisted_users,
+    separate_whitelist_by_seen,
+};
 
 #[derive(Default)]
 struct Config {
@@ -81,8 +86,12 @@ impl ColumnOperations<WhiteListRowData, ColumnName, Config> for ColumnName {
             )
             .interact(Sense::drag());
 
-        if show_tooltip {
-            label = label.on_hover_text(row_text);
+        // The saved user can only be used by the session that observed it
+        let seen_by = format!("Seen by session: {}", row_data.seen_by);
+        label = if show_tooltip {
+            label.on_hover_text(format!("{row_text}\n{seen_by}"))
+        } else {
+            label.on_hover_text(seen_by)
         };
         label.context_menu(|ui| {
             if ui.button("Deleted Selected").clicked() {
@@ -119,6 +128,16 @@ impl WhiteListRowData {
             seen_by,
         }
     }
+
+    fn to_listed_user(&self) -> ListedUser {
+        ListedUser {
+            name: self.name.clone(),
+            username: self.username.clone(),
+            id: self.id,
+            belongs_to: self.belongs_to.clone(),
+            seen_by: self.seen_by.clone(),
+        }
+    }
 }
 
 pub struct WhitelistData {
@@ -126,6 +145,14 @@ pub struct WhitelistData {
     target_username: String,
     failed_whitelist: i32,
     all_ids: HashSet<i64>,
+    /// Total saved users that are being loaded
+    load_total: usize,
+    /// Saved users processed so far during loading
+    load_done: usize,
+    /// Saved users that were not loaded, kept so saving does not remove them
+    unloaded: Vec<PackedWhitelistedUser>,
+    /// Saved users of other sessions that saw an already added user, keyed by the user id
+    other_sessions: HashMap<i64, Vec<PackedWhitelistedUser>>,
 }
 
 impl Default for WhitelistData {
@@ -144,6 +171,10 @@ impl Default for WhitelistData {
             target_username: String::new(),
             failed_whitelist: 0,
             all_ids: HashSet::new(),
+            load_total: 0,
+            load_done: 0,
+            unloaded: Vec::new(),
+            other_sessions: HashMap::new(),
         }
     }
 }
@@ -158,14 +189,32 @@ impl WhitelistData {
         belongs_to: Chat,
         seen_by: String,
     ) {
-        let name = if name.is_empty() {
-            String::from("Deleted Account")
-        } else {
-            name
-        };
+        let name = display_name(&name);
+
+        // The same user can be seen by multiple sessions. Keep only the first row but save the
+        // entry of every session so none of them are lost
+        if !self.all_ids.insert(id) {
+            let row_seen_by = self
+                .table
+                .get_all_rows()
+                .values()
+                .find(|row| row.row_data.id == id)
+                .map(|row| row.row_data.seen_by.clone());
+            let packed = self.other_sessions.entry(id).or_default();
+
+            if row_seen_by.as_ref() != Some(&seen_by)
+                && !packed.iter().any(|user| user.seen_by == seen_by)
+            {
+                info!("{name} is already in the whitelist, keeping the entry seen by {seen_by}");
+                packed.push(PackedWhitelistedUser::new(
+                    belongs_to.pack().to_hex(),
+                    seen_by,
+                ));
+            }
+            return;
+        }
 
         info!("Adding {name} to whitelist, seen by {seen_by}");
-        self.all_ids.insert(id);
         self.table.add_modify_row(|_rows| {
             let to_add = WhiteListRowData::new(name, username, id, belongs_to, seen_by);
             Some(to_add)
@@ -189,45 +238,79 @@ impl WhitelistData {
             ));
         });
 
+        packed_chats.extend(self.unloaded.iter().cloned());
+        packed_chats.extend(self.other_sessions.values().flatten().cloned());
         save_whitelisted_users(packed_chats, overwrite);
     }
 
+    /// Keeps saved users that could not be loaded so they stay in the saved list
+    pub fn keep_unloaded(&mut self, users: Vec<PackedWhitelistedUser>) {
+        self.unloaded.extend(users);
+    }
+
     /// Removes selected row from whitelist and saves the result
-    fn remove_selected(&mut self) -> Vec<i64> {
+    fn remove_selected(&mut self) -> Vec<ListedUser> {
         let active_rows = self.table.get_selected_rows();
 
-        let mut row_ids = Vec::new();
+        let mut removed_users = Vec::new();
         for i in &active_rows {
             info!(
                 "Removing user {} | {} from whitelist",
                 i.row_data.username, i.row_data.id
             );
             self.all_ids.remove(&i.row_data.id);
+            self.other_sessions.remove(&i.row_data.id);
             self.table.add_modify_row(|rows| {
                 rows.remove(&i.id);
-                row_ids.push(i.row_data.id);
+                removed_users.push(i.row_data.to_listed_user());
                 None
             });
         }
         self.save_whitelisted_users(true);
-        row_ids
+        removed_users
     }
 
     /// Removes all row from whitelist and saves the result
-    fn remove_all(&mut self) -> Vec<i64> {
+    fn remove_all(&mut self) -> Vec<ListedUser> {
         info!("Removing all users from whitelist");
-        let row_keys = self.all_ids.iter().copied().collect();
+        let removed_users = self
+            .table
+            .get_all_rows()
+            .values()
+            .map(|row| row.row_data.to_listed_user())
+            .collect();
         self.table.clear_all_rows();
+        self.unloaded.clear();
+        self.other_sessions.clear();
         self.save_whitelisted_users(true);
         self.all_ids.clear();
 
-        row_keys
+        removed_users
+    }
+
+    /// Removes the given users from whitelist and saves the result
+    pub fn remove_users(&mut self, user_ids: &[i64]) {
+        for id in user_ids {
+            self.all_ids.remove(id);
+            self.other_sessions.remove(id);
+        }
+        self.table.add_modify_row(|rows| {
+            rows.retain(|_, row| !user_ids.contains(&row.row_data.id));
+            None
+        });
+        self.save_whitelisted_users(true);
     }
 
     pub fn clear_text_box(&mut self) {
         self.target_username.clear();
     }
 
+    /// Add to the number of saved users processed and return the processed and total count
+    pub fn add_load_progress(&mut self, count: usize) -> (usize, usize) {
+        self.load_done += count;
+        (self.load_done, self.load_total)
+    }
+
     pub fn increase_failed_by(&mut self, count: i32) {
         self.failed_whitelist += count;
     }
@@ -245,13 +328,8 @@ impl MainWindow {
     pub fn show_whitelist_ui(&mut self, ui: &mut Ui) {
         if self.whitelist.table.config.deleted_selected {
             self.whitelist.table.config.deleted_selected = false;
-            let rows = self.whitelist.table.get_selected_rows();
-            let total_to_remove = rows.len();
-            let deleted_ids: Vec<i64> = rows.iter().map(|row| row.row_data.id).collect();
-
-            self.table().remove_whitelist(&deleted_ids);
-            self.whitelist.remove_selected();
-            self.process_state = ProcessState::WhitelistedUserRemoved(total_to_remove);
+            let deleted = self.whitelist.remove_selected();
+            self.whitelist_users_removed(deleted);
         }
 
         Grid::new("Whitelist Grid")
@@ -303,12 +381,8 @@ then right click on User Table to whitelist",
                 .on_hover_text("Delete selected users from whitelist")
                 .clicked()
             {
-                let deleted: Vec<i64> = self.whitelist.remove_selected();
-                let total_to_remove = deleted.len();
-
-                self.table().remove_whitelist(&deleted);
-                self.chart().reset_saved_bars();
-                self.process_state = ProcessState::WhitelistedUserRemoved(total_to_remove);
+                let deleted = self.whitelist.remove_selected();
+                self.whitelist_users_removed(deleted);
             };
             if ui
                 .button("Delete All")
@@ -316,9 +390,7 @@ then right click on User Table to whitelist",
                 .clicked()
             {
                 let deleted = self.whitelist.remove_all();
-
-                self.table().remove_whitelist(&deleted);
-                self.chart().reset_saved_bars();
+                self.whitelist_users_removed(deleted);
                 self.process_state = ProcessState::AllWhitelistRemoved;
             };
         });
@@ -337,26 +409,53 @@ then right click on User Table to whitelist",
         });
     }
 
+    /// Clears the whitelist status of the removed users and keeps them for undo
+    fn whitelist_users_removed(&mut self, deleted: Vec<ListedUser>) {
+        let deleted_ids: Vec<i64> = deleted.iter().map(|user| user.id).collect();
+
+        self.table().remove_whitelist(&deleted_ids);
+        self.chart().reset_saved_bars();
+        self.process_state = ProcessState::WhitelistedUserRemoved(deleted.len());
+        if !deleted.is_empty() {
+            self.last_action = Some(UndoAction::WhitelistRemoved(deleted));
+        }
+    }
+
     pub fn load_whitelisted_users(&mut self) {
         // This function will never be called if there are no sessions detected.
         // Unnecessary to handle in case `self.tg_clients` is empty
 
         let all_whitelisted_users = get_whitelisted();
 
-        if all_whitelisted_users.is_err() {
+        let Ok((whitelisted_users, dropped)) = all_whitelisted_users else {
             // This case means it failed to deserialize the json or is using the old whitelist json format
-            // All previous data will be removed
-            error!("Failed to deserialize a whitelist users json file. Deleting saved json data");
-            self.whitelist.save_whitelisted_users(true);
+            // The previous data is moved to a backup file and the saved list starts empty. If the
+            // backup fails, the file is kept as it is
+            error!("Failed to deserialize a whitelist users json file");
+            if backup_whitelisted() {
+                info!("Deleting saved whitelist json data");
+                self.whitelist.save_whitelisted_users(true);
+            }
             self.process_state = ProcessState::FailedLoadWhitelistedUsers;
             self.is_processing = false;
             return;
-        }
+        };
+
+        // Malformed entries are dropped while reading, count them as failed to load
+        self.whitelist.increase_failed_by(dropped as i32);
 
         // separate whitelist data by seen_by as the key and hex as the value
-        let separated_data = separate_whitelist_by_seen(all_whitelisted_users.unwrap());
+        let separated_data = separate_whitelist_by_seen(whitelisted_users);
+
+        self.whitelist.load_done = 0;
+        self.whitelist.unloaded.clear();
+        self.whitelist.other_sessions.clear();
+        self.whitelist.load_total = separated_data.values().map(Vec::len).sum();
 
         if separated_data.is_empty() {
+            if dropped > 0 {
+                self.process_state = ProcessState::LoadedWhitelistedUsers(0, dropped as i32);
+            }
             self.is_processing = false;
             return;
         }
@@ -372,6 +471,13 @@ then right click on User Table to whitelist",
                     "{seen_by} client does not exist! Ignoring {total_whitelist} whitelisted users"
                 );
                 self.whitelist.increase_failed_by(total_whitelist as i32);
+                self.whitelist.keep_unloaded(
+                    hex_data
+                        .into_iter()
+                        .map(|hex| PackedWhitelistedUser::new(hex, seen_by.clone()))
+                        .collect(),
+                );
+                self.whitelist.add_load_progress(total_whitelist);
 
                 let success_whitelist = self.whitelist.row_len();
                 let failed_whitelist = self.whitelist.failed_whitelist_num();
@@ -379,9 +485,10 @@ then right click on User Table to whitelist",
                     ProcessState::LoadedWhitelistedUsers(success_whitelist, failed_whitelist);
                 continue;
             };
+            let cancel = self.cancel_load.clone();
             self.runtime.spawn(async move {
                 tg_client
-                    .start_process(ProcessStart::LoadWhitelistedUsers(hex_data))
+                    .start_process(ProcessStart::LoadWhitelistedUsers(hex_data, cancel))
                     .await;
             });
         }
@@ -1,5 +1,11 @@
 mod animated_label;
+mod progress_ring;
 mod row_label;
+mod sparkline;
+mod word_cloud;
 
 pub use animated_label::*;
+pub use progress_ring::*;
 pub use row_label::*;
+pub use sparkline::*;
+pub use word_cloud::*;
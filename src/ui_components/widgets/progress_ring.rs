@@ -0,0 +1,87 @@
+use eframe::egui::{
+    pos2, vec2, Align2, Id, Response, Sense, Shape, Stroke, TextStyle, Ui, Widget, WidgetInfo,
+    WidgetType,
+};
+use std::f32::consts::TAU;
+
+/// Number of points used to draw a full circle
+const RING_POINTS: usize = 64;
+/// How long it takes for the ring to catch up with a new progress value
+const ANIMATION_TIME: f32 = 0.3;
+
+/// A circular progress indicator with the percentage in the middle
+pub struct ProgressRing {
+    /// Progress between 0.0 and 1.0
+    progress: f32,
+    size: f32,
+    id: Id,
+}
+
+impl ProgressRing {
+    pub fn new(progress: f32, size: f32, id: Id) -> Self {
+        Self {
+            progress: progress.clamp(0.0, 1.0),
+            size,
+            id,
+        }
+    }
+}
+
+impl Widget for ProgressRing {
+    fn ui(self, ui: &mut Ui) -> Response {
+        let Self { progress, size, id } = self;
+
+        let (rect, response) = ui.allocate_exact_size(vec2(size, size), Sense::hover());
+        response.widget_info(|| WidgetInfo::labeled(WidgetType::ProgressIndicator, true, ""));
+
+        if !ui.is_rect_visible(rect) {
+            return response;
+        }
+
+        // Move towards the new value instead of jumping to it
+        let shown_progress = ui
+            .ctx()
+            .animate_value_with_time(id, progress, ANIMATION_TIME);
+
+        let visuals = ui.visuals();
+        let stroke_width = size / 10.0;
+        let radius = (size - stroke_width) / 2.0;
+        let center = rect.center();
+        let painter = ui.painter();
+
+        painter.circle_stroke(
+            center,
+            radius,
+            Stroke::new(stroke_width, visuals.extreme_bg_color),
+        );
+
+        // Start at the top and go clockwise
+        let total_points = (RING_POINTS as f32 * shown_progress).ceil() as usize;
+        if total_points > 0 {
+            let points = (0..=total_points)
+                .map(|index| {
+                    let angle = TAU * shown_progress * index as f32 / total_points as f32;
+                    let angle = angle - TAU / 4.0;
+                    pos2(
+                        center.x + radius * angle.cos(),
+                        center.y + radius * angle.sin(),
+                    )
+                })
+                .collect();
+            painter.add(Shape::line(
+                points,
+                Stroke::new(stroke_width, visuals.selection.bg_fill),
+            ));
+        }
+
+        painter.text(
+            center,
+            Align2::CENTER_CENTER,
+            format!("{:.0}%", shown_progress * 100.0),
+            TextStyle::Small.resolve(ui.style()),
+            visuals.text_color(),
+        );
+
+        response
+    }
+}
@@ -7,14 +7,26 @@ pub struct RowLabel {
     text: WidgetText,
     selected: bool,
     whitelisted: bool,
+    /// Value between 0.0 and 1.0 used to tint the background. 0.0 means no tint
+    intensity: f32,
+    /// Value between 0.0 and 1.0 used to highlight newly added rows. 0.0 means no highlight
+    highlight: f32,
 }
 
 impl RowLabel {
-    pub fn new(selected: bool, whitelisted: bool, text: impl Into<WidgetText>) -> Self {
+    pub fn new(
+        selected: bool,
+        whitelisted: bool,
+        intensity: f32,
+        highlight: f32,
+        text: impl Into<WidgetText>,
+    ) -> Self {
         Self {
             selected,
             text: text.into(),
             whitelisted,
+            intensity,
+            highlight,
         }
     }
 }
@@ -25,6 +37,8 @@ impl Widget for RowLabel {
             selected,
             text,
             whitelisted,
+            intensity,
+            highlight,
         } = self;
         let button_padding = ui.spacing().button_padding;
         let total_extra = button_padding + button_padding;
@@ -73,6 +87,13 @@ impl Widget for RowLabel {
                     Color32::from_rgb(255, 127, 80),
                     visuals.bg_stroke,
                 );
+            } else if highlight > 0.0 {
+                let rect = rect.expand(visuals.expansion);
+                let alpha = (highlight.clamp(0.0, 1.0) * 160.0) as u8;
+                let color = Color32::from_rgba_unmultiplied(230, 190, 60, alpha);
+
+                ui.painter()
+                    .rect(rect, visuals.rounding, color, visuals.bg_stroke);
             } else if whitelisted {
                 let rect = rect.expand(visuals.expansion);
                 let is_dark_theme = ui.visuals().dark_mode;
@@ -83,6 +104,25 @@ impl Widget for RowLabel {
                     Color32::from_rgb(255, 160, 122)
                 };
 
+                ui.painter()
+                    .rect(rect, visuals.rounding, color, visuals.bg_stroke);
+            } else if intensity > 0.0 {
+                let rect = rect.expand(visuals.expansion);
+                let is_dark_theme = ui.visuals().dark_mode;
+
+                let base_color = if is_dark_theme {
+                    Color32::from_rgb(70, 140, 90)
+                } else {
+                    Color32::from_rgb(120, 200, 140)
+                };
+                let alpha = (intensity.clamp(0.0, 1.0) * 120.0) as u8;
+                let color = Color32::from_rgba_unmultiplied(
+                    base_color.r(),
+                    base_color.g(),
+                    base_color.b(),
+                    alpha,
+                );
+
                 ui.painter()
                     .rect(rect, visuals.rounding, color, visuals.bg_stroke);
             }
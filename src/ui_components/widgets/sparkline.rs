@@ -0,0 +1,46 @@
+use eframe::egui::{pos2, Painter, Rect, Shape, Stroke};
+
+/// Characters used to show a sparkline as text, from the lowest to the highest value
+const SPARK_CHARS: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+
+/// Draws a line going through all the values scaled to fit within the rect
+pub fn paint_sparkline(painter: &Painter, rect: Rect, values: &[u32], stroke: Stroke) {
+    let Some(max_value) = values.iter().max().copied() else {
+        return;
+    };
+
+    let max_value = max_value.max(1) as f32;
+    let step = if values.len() > 1 {
+        rect.width() / (values.len() - 1) as f32
+    } else {
+        0.0
+    };
+
+    let mut points: Vec<_> = values
+        .iter()
+        .enumerate()
+        .map(|(index, value)| {
+            let x = rect.left() + step * index as f32;
+            let y = rect.bottom() - rect.height() * (*value as f32 / max_value);
+            pos2(x, y)
+        })
+        .collect();
+
+    // A single value would not draw anything so stretch it across the rect
+    if points.len() == 1 {
+        points.push(pos2(rect.right(), points[0].y));
+    }
+
+    painter.add(Shape::line(points, stroke));
+}
+
+/// Converts the values to a text sparkline that can be copied
+pub fn sparkline_text(values: &[u32]) -> String {
+    let max_value = values.iter().max().copied().unwrap_or_default().max(1);
+    let last_index = SPARK_CHARS.len() - 1;
+
+    values
+        .iter()
+        .map(|value| SPARK_CHARS[*value as usize * last_index / max_value as usize])
+        .collect()
+}
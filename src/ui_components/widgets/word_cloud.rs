@@ -0,0 +1,117 @@
+use eframe::egui::{vec2, Color32, FontId, Galley, Rect, Sense, Ui, Vec2};
+use std::sync::Arc;
+
+/// Font size of the least used word in the cloud
+const MIN_FONT_SIZE: f32 = 12.0;
+/// Font size of the most used word in the cloud
+const MAX_FONT_SIZE: f32 = 56.0;
+/// How many positions along the spiral are tried for a word before it is left out
+const MAX_SPIRAL_STEPS: usize = 1500;
+
+/// Placed words of a word cloud for a given size. Rects are relative to the top left of the
+/// cloud so it can be repainted without placing the words again
+pub struct WordCloudLayout {
+    size: Vec2,
+    placed: Vec<(Rect, usize)>,
+}
+
+/// Draws the words in the available space, sized by their count and placed along a spiral
+/// starting from the center. The words are expected to be sorted by count, highest first.
+/// Words that do not fit are left out. The placement is saved in `layout` and only redone when
+/// the available size changes or the layout is cleared
+pub fn show_word_cloud(
+    ui: &mut Ui,
+    words: &[(String, u64)],
+    layout: &mut Option<WordCloudLayout>,
+    color: impl Fn(usize) -> Color32,
+) {
+    let (rect, response) = ui.allocate_exact_size(ui.available_size(), Sense::hover());
+
+    let (Some((_, max_count)), Some((_, min_count))) = (words.first(), words.last()) else {
+        return;
+    };
+    let count_range = (max_count - min_count).max(1) as f32;
+
+    let painter = ui.painter_at(rect);
+    let galley = |index: usize| -> Arc<Galley> {
+        let (word, count) = &words[index];
+        // Square root keeps the less used words readable next to the most used ones
+        let scale = ((count - min_count) as f32 / count_range).sqrt();
+        let font_size = MIN_FONT_SIZE + (MAX_FONT_SIZE - MIN_FONT_SIZE) * scale;
+        painter.layout_no_wrap(word.clone(), FontId::proportional(font_size), color(index))
+    };
+
+    if layout
+        .as_ref()
+        .is_none_or(|layout| layout.size != rect.size())
+    {
+        *layout = Some(WordCloudLayout {
+            size: rect.size(),
+            placed: place_words(rect.size(), words.len(), |index| galley(index).size()),
+        });
+    }
+    let Some(layout) = layout else {
+        return;
+    };
+
+    for (word_rect, index) in &layout.placed {
+        painter.galley(
+            rect.min + word_rect.min.to_vec2(),
+            galley(*index),
+            Color32::PLACEHOLDER,
+        );
+    }
+
+    if let Some(pointer) = response.hover_pos() {
+        let pointer = pointer - rect.min.to_vec2();
+        if let Some((_, index)) = layout
+            .placed
+            .iter()
+            .find(|(word_rect, _)| word_rect.contains(pointer))
+        {
+            let (word, count) = &words[*index];
+            response.on_hover_text_at_pointer(format!("{word}\nUsed {count} times"));
+        }
+    }
+}
+
+/// Places the words along a spiral within the given size and returns the rect of each placed
+/// word with its index
+fn place_words(
+    size: Vec2,
+    total_words: usize,
+    word_size: impl Fn(usize) -> Vec2,
+) -> Vec<(Rect, usize)> {
+    let bounds = Rect::from_min_size(Default::default(), size);
+    let center = bounds.center();
+    // Stretch the spiral horizontally as words are wider than they are tall
+    let aspect = size.x / size.y.max(1.0);
+
+    let mut placed: Vec<(Rect, usize)> = Vec::new();
+
+    for index in 0..total_words {
+        let size = word_size(index);
+
+        for step in 0..MAX_SPIRAL_STEPS {
+            let angle = step as f32 * 0.1;
+            let radius = angle * 2.0;
+            let offset = vec2(radius * angle.cos() * aspect, radius * angle.sin());
+            let word_rect = Rect::from_center_size(center + offset, size);
+
+            if !bounds.contains_rect(word_rect) {
+                continue;
+            }
+            if placed
+                .iter()
+                .any(|(other, _)| other.expand(2.0).intersects(word_rect))
+            {
+                continue;
+            }
+
+            placed.push((word_rect, index));
+            break;
+        }
+    }
+
+    placed
+}
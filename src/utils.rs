@@ -1,19 +1,31 @@
 use chrono::{Local, NaiveDate, NaiveDateTime};
-use egui_selectable_table::SelectableRow;
+use egui_selectable_table::{ColumnOperations, SelectableRow};
+use grammers_client::types::{Media, Message};
 use log::{error, info};
+use serde::de::DeserializeOwned;
 use serde::{Deserialize, Serialize};
 use std::collections::{BTreeMap, HashMap, HashSet};
 use std::error::Error;
 use std::fs::{self, File};
-use std::io::{Read, Write};
-use std::path::PathBuf;
+use std::io::{BufWriter, Read, Write};
+use std::path::{Path, PathBuf};
+use strum::IntoEnumIterator;
 use tokio::runtime::{self, Runtime};
+use unicode_segmentation::UnicodeSegmentation;
 
 use crate::ui_components::processor::{
-    ChartTiming, ColumnName, PackedBlacklistedUser, PackedWhitelistedUser, ParsedChat,
+    AppSettings, ChartTiming, ColumnName, CounterCounts, CsvDelimiter, DateFormat,
+    PackedBlacklistedUser, PackedWhitelistedUser, ParsedChat, StreamedMessage,
 };
-use crate::ui_components::tab_ui::UserRowData;
-use crate::ui_components::TGKeys;
+use crate::ui_components::tab_ui::{ChartsData, DateTotals, UserRowData, UserTableData};
+use crate::ui_components::{KeyProfiles, TGKeys, DEFAULT_KEY_PROFILE};
+
+/// Label used for users whose account was deleted
+pub const DELETED_ACCOUNT_LABEL: &str = "Deleted Account";
+/// Label used for messages without any sender information
+pub const ANONYMOUS_LABEL: &str = "Anonymous/Unknown";
+/// Chat name used for the Saved Messages of the session
+pub const SAVED_MESSAGES_CHAT: &str = "me";
 
 #[derive(Serialize, Deserialize)]
 pub struct IsLightTheme {
@@ -68,46 +80,73 @@ pub fn chat_to_text(start: &str, end: &str) -> String {
 
 /// Parse start and end point strings as parsed chat points
 pub fn parse_chat_details(start: &str, end: &str) -> HashMap<String, ParsedChat> {
-    let start_chat_list: Vec<&str> = start.split_whitespace().collect();
-    let end_chat_list: Vec<&str> = end.split_whitespace().collect();
-
     let mut parsed_chat_list = HashMap::new();
 
-    for chat in start_chat_list {
-        let (name, num) = parse_tg_chat(chat);
-        if name.is_none() {
-            error!("{chat} is getting ignored as no chat name was found");
-            continue;
+    for (name, start_num, end_num) in parse_chat_points(start) {
+        let mut parsed = ParsedChat::new(name.clone(), start_num, None);
+        if let Some(end_num) = end_num {
+            if !parsed.set_end_point(end_num) {
+                error!("End point cannot be equal or bigger than start point. Ignoring the end point for {name}");
+            }
         }
-
-        let name = name.unwrap();
-        let parsed = ParsedChat::new(name.clone(), num, None);
         parsed_chat_list.insert(name, parsed);
     }
 
-    for chat in end_chat_list {
-        let (name, num) = parse_tg_chat(chat);
-        if name.is_none() {
-            error!("{chat} is getting ignored as no chat name was found");
-            continue;
-        }
-
-        let name = name.unwrap();
+    for (name, num, _) in parse_chat_points(end) {
         if let Some(parsed) = parsed_chat_list.get_mut(&name) {
             if let Some(end_num) = num {
                 let completed = parsed.set_end_point(end_num);
                 if !completed {
-                    error!("End point cannot be equal or bigger than start point. Ignoring the end point for {chat}");
+                    error!("End point cannot be equal or bigger than start point. Ignoring the end point for {name}");
                 }
             }
         } else {
-            error!("{chat} was not found in the start point, this will be ignored");
+            error!("{name} was not found in the start point, this will be ignored");
         }
     }
 
     parsed_chat_list
 }
 
+/// Parses space separated chats where each chat can be followed by up to two bare message numbers
+/// Example: chat_name 1000 500 or t.me/chat_name/1000 500
+///
+/// A bare number is always a message number. Numeric chat IDs are written with a slash such as
+/// 1234567890/1000 or 1234567890/ so they are never mistaken for one
+fn parse_chat_points(text: &str) -> Vec<(String, Option<i32>, Option<i32>)> {
+    let mut chats: Vec<(String, Option<i32>, Option<i32>)> = Vec::new();
+
+    for chat in text.split_whitespace() {
+        if chat.parse::<i64>().is_ok() {
+            // A bare number after a chat is the next missing message number of that chat
+            let missing_num = chats.last_mut().and_then(|(_, first_num, second_num)| {
+                if first_num.is_none() {
+                    Some(first_num)
+                } else if second_num.is_none() {
+                    Some(second_num)
+                } else {
+                    None
+                }
+            });
+
+            match (missing_num, chat.parse()) {
+                (Some(missing_num), Ok(num)) => *missing_num = Some(num),
+                _ => error!("{chat} is getting ignored as it is not a message number of a chat. Chat IDs are written as {chat}/"),
+            }
+            continue;
+        }
+
+        let (name, num) = parse_tg_chat(chat);
+        let Some(name) = name else {
+            error!("{chat} is getting ignored as no chat name was found");
+            continue;
+        };
+        chats.push((name, num, None));
+    }
+
+    chats
+}
+
 /// Tries to parse a link to get the chat name and the message ID
 pub fn parse_tg_chat(text: &str) -> (Option<String>, Option<i32>) {
     if text.is_empty() {
@@ -125,7 +164,10 @@ pub fn parse_tg_chat(text: &str) -> (Option<String>, Option<i32>) {
             // It will be either chat_name/number or chat_name
             // if chat_name/number split it again and get the number
             // otherwise set whatever value is remaining as the parsed chat name
-            if second.contains('/') {
+            // Private channel links are in the form of c/channel_id/number
+            if let Some(private_link) = second.strip_prefix("c/") {
+                (chat_name, message_number) = split_private_link(private_link);
+            } else if second.contains('/') {
                 (chat_name, message_number) = split_tg_link(second);
             } else {
                 chat_name = Some(second.to_string());
@@ -150,9 +192,30 @@ pub fn parse_tg_chat(text: &str) -> (Option<String>, Option<i32>) {
             chat_name = Some(text.to_string());
         };
     }
+
+    let chat_name = chat_name.map(|name| {
+        if is_saved_messages(&name) {
+            SAVED_MESSAGES_CHAT.to_string()
+        } else {
+            name
+        }
+    });
     (chat_name, message_number)
 }
 
+/// Whether the chat name points to the Saved Messages of the session
+fn is_saved_messages(name: &str) -> bool {
+    ["me", "self"]
+        .iter()
+        .any(|target| name.eq_ignore_ascii_case(target))
+}
+
+/// Whether the chat name resolves to a different chat for each session. Saved Messages and
+/// numeric IDs are looked up in the session's own account and dialogs
+pub fn is_session_bound_chat(name: &str) -> bool {
+    name == SAVED_MESSAGES_CHAT || name.parse::<i64>().is_ok()
+}
+
 /// Splits a string on slash and tries to get the tg chat name and message number
 fn split_tg_link(text: &str) -> (Option<String>, Option<i32>) {
     let mut chat_name = None;
@@ -168,6 +231,23 @@ fn split_tg_link(text: &str) -> (Option<String>, Option<i32>) {
     (chat_name, message_number)
 }
 
+/// Splits a private channel link to get the channel ID as the chat name and the message number
+fn split_private_link(text: &str) -> (Option<String>, Option<i32>) {
+    let (channel_id, message_number) = if text.contains('/') {
+        split_tg_link(text)
+    } else {
+        (Some(text.to_string()), None)
+    };
+
+    // A private channel link without a valid ID cannot be resolved
+    let channel_id = channel_id.filter(|id| id.parse::<i64>().is_ok());
+    if channel_id.is_none() {
+        return (None, None);
+    }
+
+    (channel_id, message_number)
+}
+
 /// Returns the proper emoji based on light or dark value
 pub fn theme_hover_text(is_light_theme: bool) -> String {
     if is_light_theme {
@@ -186,32 +266,35 @@ pub fn get_runtime() -> Runtime {
         .unwrap()
 }
 
-/// Tries to read the API key json file
+/// Returns the keys of the active API key profile
 pub fn get_api_keys() -> Option<TGKeys> {
-    let mut to_return = None;
+    get_key_profiles()?.active_keys().cloned()
+}
+
+/// Tries to read the API key json file. A file with a single key pair from the older versions
+/// is moved to the default profile
+pub fn get_key_profiles() -> Option<KeyProfiles> {
     let mut api_key_path = PathBuf::from(".");
     api_key_path.push("api_keys.json");
 
-    let file = File::open(api_key_path);
+    let mut file = File::open(api_key_path).ok()?;
+    let mut contents = String::new();
+    file.read_to_string(&mut contents)
+        .expect("Failed to read file");
 
-    if let Ok(mut file) = file {
-        let mut contents = String::new();
-        file.read_to_string(&mut contents)
-            .expect("Failed to read file");
-
-        let result = serde_json::from_str::<TGKeys>(&contents);
-        if let Ok(result) = result {
-            if !result.api_id.is_empty() && !result.api_hash.is_empty() {
-                to_return = Some(result);
-            }
-        }
+    if let Ok(profiles) = serde_json::from_str::<KeyProfiles>(&contents) {
+        return Some(profiles);
     }
 
-    to_return
+    let keys = serde_json::from_str::<TGKeys>(&contents).ok()?;
+    info!("Moving the saved API keys to the {DEFAULT_KEY_PROFILE} profile");
+    let profiles = KeyProfiles::from_keys(keys);
+    save_key_profiles(&profiles);
+    Some(profiles)
 }
 
-/// Saves the API keys in a json file
-pub fn save_api_keys(api_keys: &TGKeys) {
+/// Saves the API key profiles in a json file
+pub fn save_key_profiles(api_keys: &KeyProfiles) {
     let data = serde_json::to_string(api_keys);
 
     if let Ok(data) = data {
@@ -223,38 +306,112 @@ pub fn save_api_keys(api_keys: &TGKeys) {
 }
 
 /// Reads the whitelisted user `PackedChat` Hex IDs and returns them
-pub fn get_whitelisted() -> Result<Vec<PackedWhitelistedUser>, Box<dyn Error>> {
+pub fn get_whitelisted() -> Result<(Vec<PackedWhitelistedUser>, usize), Box<dyn Error>> {
     let mut whitelist_path = PathBuf::from(".");
     whitelist_path.push("whitelist.json");
 
-    let file = File::open(whitelist_path);
-
-    if let Ok(mut file) = file {
-        let mut contents = String::new();
-        file.read_to_string(&mut contents)?;
-        let users = serde_json::from_str(&contents)?;
-        Ok(users)
-    } else {
-        Ok(Vec::new())
-    }
+    read_saved_users(&whitelist_path)
 }
 
 /// Reads the blacklisted user `PackedChat` Hex IDs and returns them
-pub fn get_blacklisted() -> Result<Vec<PackedBlacklistedUser>, Box<dyn Error>> {
+pub fn get_blacklisted() -> Result<(Vec<PackedBlacklistedUser>, usize), Box<dyn Error>> {
     let mut blacklist_path = PathBuf::from(".");
     blacklist_path.push("blacklist.json");
 
-    let file = File::open(blacklist_path);
+    read_saved_users(&blacklist_path)
+}
 
-    if let Ok(mut file) = file {
-        let mut contents = String::new();
-        file.read_to_string(&mut contents)?;
-        let users = serde_json::from_str(&contents)?;
-        Ok(users)
-    } else {
-        Ok(Vec::new())
+/// Copies the whitelist json file to a `.json.bak` file. Returns false if the copy failed
+pub fn backup_whitelisted() -> bool {
+    let mut whitelist_path = PathBuf::from(".");
+    whitelist_path.push("whitelist.json");
+
+    backup_saved_users(&whitelist_path)
+}
+
+/// Copies the blacklist json file to a `.json.bak` file. Returns false if the copy failed
+pub fn backup_blacklisted() -> bool {
+    let mut blacklist_path = PathBuf::from(".");
+    blacklist_path.push("blacklist.json");
+
+    backup_saved_users(&blacklist_path)
+}
+
+/// Copies a saved user json file to a `.json.bak` file next to it so it can still be recovered
+/// by hand after being rewritten. Returns false if the file exists but could not be copied
+fn backup_saved_users(path: &Path) -> bool {
+    if !path.exists() {
+        return true;
+    }
+
+    let backup_path = path.with_extension("json.bak");
+    match fs::copy(path, &backup_path) {
+        Ok(_) => {
+            info!(
+                "Saved a copy of {} to {}",
+                path.display(),
+                backup_path.display()
+            );
+            true
+        }
+        Err(e) => {
+            error!("Failed to back up {}. Error: {e}", path.display());
+            false
+        }
     }
 }
+
+/// Reads a saved user json file entry by entry, keeping the valid entries if some are malformed.
+/// The malformed entries are removed from the file after the original is copied to a `.bak`
+/// file. The file is left untouched if it cannot be parsed at all or the copy fails. Returns the
+/// valid entries and the number of removed entries
+fn read_saved_users<T: DeserializeOwned>(path: &Path) -> Result<(Vec<T>, usize), Box<dyn Error>> {
+    let Ok(mut file) = File::open(path) else {
+        return Ok((Vec::new(), 0));
+    };
+
+    let mut contents = String::new();
+    file.read_to_string(&mut contents)?;
+    let entries: Vec<serde_json::Value> = serde_json::from_str(&contents)?;
+    let total_entries = entries.len();
+
+    let mut users = Vec::new();
+    let mut valid_entries = Vec::new();
+
+    for entry in entries {
+        match serde_json::from_value(entry.clone()) {
+            Ok(user) => {
+                users.push(user);
+                valid_entries.push(entry);
+            }
+            Err(e) => error!("Dropping a malformed saved user entry. Error: {e}"),
+        }
+    }
+
+    let dropped = total_entries - users.len();
+    if dropped > 0 {
+        info!(
+            "Recovered {} saved users from {}, dropped {dropped}",
+            users.len(),
+            path.display()
+        );
+
+        // The recovered users are used even if the file could not be rewritten
+        if backup_saved_users(path) {
+            match serde_json::to_string(&valid_entries) {
+                Ok(data) => {
+                    if let Err(e) = fs::write(path, data) {
+                        error!("Failed to rewrite {}. Error: {e}", path.display());
+                    }
+                }
+                Err(e) => error!("Failed to serialize the recovered users. Error: {e}"),
+            }
+        }
+    }
+
+    Ok((users, dropped))
+}
+
 /// Saves `PackedChat` Hex strings to a json file
 pub fn save_whitelisted_users(packed_chats: Vec<PackedWhitelistedUser>, overwrite: bool) {
     // HashSet to avoid duplicate whitelisted users
@@ -344,13 +501,44 @@ pub fn get_font_data() -> Option<(Vec<u8>, Vec<u8>)> {
 }
 
 /// Convenient function to format `NaiveDateTime` to string. Used for the Chart UI
-pub fn time_to_string(time: &NaiveDateTime, timing: ChartTiming) -> String {
+pub fn time_to_string(time: &NaiveDateTime, timing: ChartTiming, format: DateFormat) -> String {
     match timing {
-        ChartTiming::Hourly => time.to_string(),
-        _ => time.format("%Y-%m-%d").to_string(),
+        ChartTiming::Hourly | ChartTiming::Custom => format.format_time(time),
+        _ => format.format_date(time),
     }
 }
 
+/// Shortens a number for display, for example 125000 to 125k and 1200000 to 1.2M
+pub fn format_compact_number(value: f64) -> String {
+    let abs_value = value.abs();
+
+    // Avoid showing -0 from floating point errors
+    if abs_value < 0.005 {
+        return String::from("0");
+    }
+
+    let (scaled, suffix) = if abs_value >= 1_000_000_000.0 {
+        (value / 1_000_000_000.0, "B")
+    } else if abs_value >= 1_000_000.0 {
+        (value / 1_000_000.0, "M")
+    } else if abs_value >= 1_000.0 {
+        (value / 1_000.0, "k")
+    } else {
+        (value, "")
+    };
+
+    // Keep at most 1 decimal for abbreviated values and 2 for the rest
+    let precision = if suffix.is_empty() { 2 } else { 1 };
+    let formatted = format!("{scaled:.precision$}");
+    let formatted = if formatted.contains('.') {
+        formatted.trim_end_matches('0').trim_end_matches('.')
+    } else {
+        &formatted
+    };
+
+    format!("{formatted}{suffix}")
+}
+
 /// Convenient function to convert u8 to a Week name string. used for the Chart UI
 pub fn weekday_num_to_string(weekday: u8) -> String {
     match weekday {
@@ -365,19 +553,50 @@ pub fn weekday_num_to_string(weekday: u8) -> String {
     }
 }
 
-pub fn export_table_data(rows: &Vec<SelectableRow<UserRowData, ColumnName>>, name: &str) {
-    let mut export_file_location = PathBuf::from(".");
-    let current_time = Local::now();
-    let formatted_time = current_time.format("%Y-%m-%d %H-%M-%S").to_string();
-    let file_name = format!("{name} Table Export {formatted_time}.csv");
+/// Whether the message is a sticker or a GIF. These messages have no text of their own
+pub fn is_sticker_or_gif(message: &Message) -> bool {
+    match message.media() {
+        Some(Media::Sticker(_)) => true,
+        Some(Media::Document(document)) => document.is_animated(),
+        _ => false,
+    }
+}
+
+pub fn hour_num_to_string(hour: u8) -> String {
+    format!("{hour:02}:00 - {:02}:00", (hour + 1) % 24)
+}
+
+pub fn export_table_data(rows: &[UserRowData], name: &str, delimiter: CsvDelimiter) {
+    let formatted_time = Local::now().format("%Y-%m-%d %H-%M-%S").to_string();
+    write_csv(
+        rows,
+        &format!("{name} Table Export {formatted_time}.csv"),
+        delimiter,
+    );
+}
+
+/// Writes the totals of each date to a CSV file in the current directory
+pub fn export_date_totals(rows: &[DateTotals], name: &str, delimiter: CsvDelimiter) {
+    let formatted_time = Local::now().format("%Y-%m-%d %H-%M-%S").to_string();
+    write_csv(
+        rows,
+        &format!("{name} Date Export {formatted_time}.csv"),
+        delimiter,
+    );
+}
 
+/// Writes the rows to a CSV file with the given name in the current directory. Rows that fail
+/// to serialize are skipped
+fn write_csv<T: Serialize>(rows: &[T], file_name: &str, delimiter: CsvDelimiter) {
+    let mut export_file_location = PathBuf::from(".");
     export_file_location.push(file_name);
     let file = File::create(export_file_location).unwrap();
 
-    let mut wtr = csv::Writer::from_writer(file);
+    let mut wtr = csv::WriterBuilder::new()
+        .delimiter(delimiter.as_byte())
+        .from_writer(file);
 
     for row in rows {
-        let row = &row.row_data;
         if let Err(e) = wtr.serialize(row) {
             error!("Failed to add one row, skipping. Error: {e}");
         }
@@ -386,6 +605,164 @@ pub fn export_table_data(rows: &Vec<SelectableRow<UserRowData, ColumnName>>, nam
     wtr.flush().unwrap();
 }
 
+/// Reads the rows of a table CSV that was exported earlier. The delimiter is detected from the
+/// header and the columns are matched by their name so the order does not matter. Returns the
+/// chat name taken from the file name with the rows
+pub fn import_table_data(path: &str) -> Result<(String, Vec<UserRowData>), Box<dyn Error>> {
+    let path = Path::new(path.trim());
+    let mut contents = String::new();
+    File::open(path)?.read_to_string(&mut contents)?;
+
+    let file_name = path
+        .file_stem()
+        .unwrap_or_default()
+        .to_string_lossy()
+        .to_string();
+    let chat_name = match file_name.split_once(" Table Export ") {
+        Some((name, _)) => name.to_string(),
+        None => file_name,
+    };
+
+    let header = contents.lines().next().unwrap_or_default();
+    let delimiter = CsvDelimiter::iter()
+        .max_by_key(|delimiter| header.matches(delimiter.as_byte() as char).count())
+        .unwrap_or_default();
+
+    let mut rdr = csv::ReaderBuilder::new()
+        .delimiter(delimiter.as_byte())
+        .from_reader(contents.as_bytes());
+
+    let mut rows = Vec::new();
+    for row in rdr.deserialize() {
+        rows.push(row?);
+    }
+
+    if rows.is_empty() {
+        return Err("No rows found in the file".into());
+    }
+
+    Ok((format!("{chat_name} (Imported)"), rows))
+}
+
+/// Formats a user as a Markdown mention link that Telegram turns into a mention when pasted
+pub fn user_mention(name: &str, id: i64) -> String {
+    let name = name
+        .replace('\\', "\\\\")
+        .replace('[', "\\[")
+        .replace(']', "\\]");
+    format!("[{name}](tg://user?id={id})")
+}
+
+/// Formats the given rows as a GitHub-flavored Markdown table with the given columns
+pub fn table_to_markdown(
+    rows: &[SelectableRow<UserRowData, ColumnName>],
+    columns: &[ColumnName],
+) -> String {
+    let headers: Vec<String> = columns.iter().map(ToString::to_string).collect();
+    let alignments: Vec<&str> = columns
+        .iter()
+        .map(|column| match column {
+            ColumnName::Name
+            | ColumnName::Username
+            | ColumnName::FirstMessageSeen
+            | ColumnName::LastMessageSeen
+            | ColumnName::Activity
+            | ColumnName::Whitelisted
+            | ColumnName::Admin
+            | ColumnName::SeenBy => ":---",
+            _ => "---:",
+        })
+        .collect();
+
+    let mut markdown = format!("| {} |\n", headers.join(" | "));
+    markdown.push_str(&format!("| {} |\n", alignments.join(" | ")));
+
+    for row in rows {
+        let cells: Vec<String> = columns
+            .iter()
+            .map(|column| {
+                column
+                    .column_text(&row.row_data)
+                    .replace('|', "\\|")
+                    .replace('\n', " ")
+            })
+            .collect();
+        markdown.push_str(&format!("| {} |\n", cells.join(" | ")));
+    }
+
+    markdown
+}
+
+/// Creates a plain text summary of the statistics of a counted chat
+pub fn chat_summary_text(
+    chat_name: &str,
+    counts: &CounterCounts,
+    table: &UserTableData,
+    chart: &ChartsData,
+) -> String {
+    let date_range = match table.date_range() {
+        Some((first, last)) => format!("{first} to {last}"),
+        None => String::from("N/A"),
+    };
+
+    let mut summary = format!("Chat: {chat_name}\n");
+    summary.push_str(&format!("Date Range: {date_range}\n"));
+    summary.push_str(&format!("Total Messages: {}\n", counts.total_message));
+    summary.push_str(&format!("Total Users: {}\n", counts.total_user));
+    summary.push_str(&format!(
+        "Whitelisted Messages: {}\n",
+        counts.whitelisted_message
+    ));
+    summary.push_str(&format!(
+        "Whitelisted Users: {}\n",
+        counts.total_whitelisted()
+    ));
+
+    let top_users = table.top_users(5);
+    if !top_users.is_empty() {
+        summary.push_str("Top Users:\n");
+        for (index, (name, total_message)) in top_users.iter().enumerate() {
+            summary.push_str(&format!("  {}. {name}: {total_message}\n", index + 1));
+        }
+    }
+
+    let busiest_day = match chart.busiest_day() {
+        Some((date, total_message)) => format!("{date} ({total_message} messages)"),
+        None => String::from("N/A"),
+    };
+    summary.push_str(&format!("Busiest Day: {busiest_day}"));
+
+    summary
+}
+
+/// Creates a new JSONL file for streaming the counted messages of a chat
+pub fn create_stream_file(name: &str) -> Option<BufWriter<File>> {
+    let mut export_file_location = PathBuf::from(".");
+    let current_time = Local::now();
+    let formatted_time = current_time.format("%Y-%m-%d %H-%M-%S").to_string();
+    let file_name = format!("{name} Message Stream {formatted_time}.jsonl");
+
+    export_file_location.push(file_name);
+
+    match File::create(export_file_location) {
+        Ok(file) => Some(BufWriter::new(file)),
+        Err(e) => {
+            error!("Failed to create the message stream file. Error: {e}");
+            None
+        }
+    }
+}
+
+/// Appends a single message summary to the stream file as a JSON line
+pub fn write_streamed_message(
+    writer: &mut BufWriter<File>,
+    message: &StreamedMessage,
+) -> Result<(), Box<dyn Error>> {
+    serde_json::to_writer(&mut *writer, message)?;
+    writer.write_all(b"\n")?;
+    Ok(())
+}
+
 pub fn separate_whitelist_by_seen(
     whitelist_data: Vec<PackedWhitelistedUser>,
 ) -> HashMap<String, Vec<String>> {
@@ -422,10 +799,87 @@ pub fn entry_insert_user(
     entry.entry(id).or_insert(user_row_data.clone());
 }
 
+/// Whether the character belongs to a right-to-left script such as Hebrew or Arabic
+fn is_rtl_char(c: char) -> bool {
+    matches!(
+        c,
+        '\u{0590}'..='\u{08FF}'
+            | '\u{FB1D}'..='\u{FDFF}'
+            | '\u{FE70}'..='\u{FEFF}'
+            | '\u{10800}'..='\u{10FFF}'
+            | '\u{1E800}'..='\u{1EFFF}'
+    )
+}
+
+/// Whether most of the letters of the text belong to a right-to-left script
+pub fn is_rtl_text(text: &str) -> bool {
+    let mut rtl = 0;
+    let mut ltr = 0;
+
+    for c in text.chars() {
+        if is_rtl_char(c) {
+            rtl += 1;
+        } else if c.is_alphabetic() {
+            ltr += 1;
+        }
+    }
+    rtl > ltr
+}
+
+/// egui only lays out text left to right. Reorders right-to-left text so it reads correctly,
+/// keeping the order of the left-to-right parts like numbers and latin words within it
+pub fn rtl_display_text(text: &str) -> String {
+    let mut runs: Vec<(bool, Vec<&str>)> = Vec::new();
+
+    for grapheme in text.graphemes(true) {
+        let is_ltr = grapheme
+            .chars()
+            .next()
+            .is_some_and(|c| c.is_alphanumeric() && !is_rtl_char(c));
+
+        match runs.last_mut() {
+            Some((run_ltr, graphemes)) if *run_ltr == is_ltr => graphemes.push(grapheme),
+            _ => runs.push((is_ltr, vec![grapheme])),
+        }
+    }
+
+    let mut display_text = String::with_capacity(text.len());
+    for (is_ltr, mut graphemes) in runs.into_iter().rev() {
+        if !is_ltr {
+            graphemes.reverse();
+        }
+        for grapheme in graphemes {
+            // Brackets face the other way when read from right to left
+            let mirrored = match grapheme {
+                "(" => ")",
+                ")" => "(",
+                "[" => "]",
+                "]" => "[",
+                "{" => "}",
+                "}" => "{",
+                "<" => ">",
+                ">" => "<",
+                _ => grapheme,
+            };
+            display_text.push_str(mirrored);
+        }
+    }
+    display_text
+}
+
+/// Returns the name to display for a user. Empty names belong to deleted accounts
+pub fn display_name(name: &str) -> String {
+    if name.is_empty() {
+        DELETED_ACCOUNT_LABEL.to_string()
+    } else {
+        name.to_string()
+    }
+}
+
 pub fn to_chart_name(user_name: String, full_name: &str, user_id: i64) -> String {
     if user_name != "Empty" {
         user_name
-    } else if full_name == "Deleted Account" {
+    } else if full_name == DELETED_ACCOUNT_LABEL {
         user_id.to_string()
     } else {
         format!("{full_name} {user_id}")
@@ -459,3 +913,166 @@ pub fn save_theme(status: bool) {
         file.write_all(data.as_bytes()).unwrap();
     };
 }
+
+/// Loads the highest counted message ID of each chat used for incremental counting
+pub fn get_checkpoints() -> HashMap<String, i32> {
+    let mut checkpoint_json = PathBuf::from(".");
+    checkpoint_json.push("checkpoints.json");
+
+    let Ok(mut file) = File::open(checkpoint_json) else {
+        return HashMap::new();
+    };
+
+    let mut contents = String::new();
+    if file.read_to_string(&mut contents).is_err() {
+        return HashMap::new();
+    }
+
+    serde_json::from_str(&contents).unwrap_or_else(|e| {
+        error!("Failed to parse the saved checkpoints. Error: {e}");
+        HashMap::new()
+    })
+}
+
+/// Saves the highest counted message ID of a chat if it is newer than the saved one
+pub fn save_checkpoint(chat_name: &str, message_id: i32) {
+    let mut checkpoints = get_checkpoints();
+    let checkpoint = checkpoints.entry(chat_name.to_string()).or_default();
+
+    if *checkpoint >= message_id {
+        return;
+    }
+    *checkpoint = message_id;
+
+    let data = serde_json::to_string(&checkpoints);
+
+    if let Ok(data) = data {
+        let mut checkpoint_path = PathBuf::from(".");
+        checkpoint_path.push("checkpoints.json");
+        let mut file = File::create(checkpoint_path).unwrap();
+        file.write_all(data.as_bytes()).unwrap();
+    };
+}
+
+/// Loads the saved app settings or the default settings if none are saved
+pub fn get_settings() -> AppSettings {
+    let mut settings_json = PathBuf::from(".");
+    settings_json.push("settings.json");
+
+    let Ok(mut file) = File::open(settings_json) else {
+        return AppSettings::default();
+    };
+
+    let mut contents = String::new();
+    if file.read_to_string(&mut contents).is_err() {
+        return AppSettings::default();
+    }
+
+    serde_json::from_str(&contents).unwrap_or_else(|e| {
+        error!("Failed to parse the saved settings. Using the default settings. Error: {e}");
+        AppSettings::default()
+    })
+}
+
+pub fn save_settings(settings: &AppSettings) {
+    let data = serde_json::to_string(settings);
+
+    if let Ok(data) = data {
+        let mut settings_path = PathBuf::from(".");
+        settings_path.push("settings.json");
+        let mut file = File::create(settings_path).unwrap();
+        file.write_all(data.as_bytes()).unwrap();
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn point(
+        name: &str,
+        first: Option<i32>,
+        second: Option<i32>,
+    ) -> (String, Option<i32>, Option<i32>) {
+        (name.to_string(), first, second)
+    }
+
+    #[test]
+    fn parses_bare_message_numbers() {
+        assert_eq!(
+            parse_chat_points("chat_name 1000 500"),
+            vec![point("chat_name", Some(1000), Some(500))]
+        );
+        assert_eq!(
+            parse_chat_points("t.me/chat_name/1000 500"),
+            vec![point("chat_name", Some(1000), Some(500))]
+        );
+        // Numbers past the two message numbers of a chat are ignored
+        assert_eq!(
+            parse_chat_points("chat_name 3 2 1"),
+            vec![point("chat_name", Some(3), Some(2))]
+        );
+    }
+
+    #[test]
+    fn parses_numeric_chat_ids() {
+        // A bare number is always a message number, never a chat ID
+        assert_eq!(
+            parse_chat_points("chat_name 1234567890"),
+            vec![point("chat_name", Some(1234567890), None)]
+        );
+        assert!(parse_chat_points("1234567890").is_empty());
+        assert!(parse_chat_points("9876543210 100").is_empty());
+
+        // Chat IDs need a slash
+        assert_eq!(
+            parse_chat_points("chat_name 1234567890/ 1000"),
+            vec![
+                point("chat_name", None, None),
+                point("1234567890", Some(1000), None)
+            ]
+        );
+        assert_eq!(
+            parse_chat_points("1234567890/100 50"),
+            vec![point("1234567890", Some(100), Some(50))]
+        );
+    }
+
+    #[test]
+    fn parses_saved_messages() {
+        assert_eq!(parse_tg_chat("me/5"), (Some("me".to_string()), Some(5)));
+        assert_eq!(parse_tg_chat("Self"), (Some("me".to_string()), None));
+        assert_eq!(parse_tg_chat("saved"), (Some("saved".to_string()), None));
+    }
+
+    #[test]
+    fn splits_private_links() {
+        assert_eq!(
+            split_private_link("1234567890/42"),
+            (Some("1234567890".to_string()), Some(42))
+        );
+        assert_eq!(
+            split_private_link("1234567890"),
+            (Some("1234567890".to_string()), None)
+        );
+        assert_eq!(split_private_link("chat_name/42"), (None, None));
+        assert_eq!(
+            parse_tg_chat("https://t.me/c/1234567890/42"),
+            (Some("1234567890".to_string()), Some(42))
+        );
+    }
+
+    #[test]
+    fn formats_compact_numbers() {
+        assert_eq!(format_compact_number(0.0), "0");
+        assert_eq!(format_compact_number(-0.001), "0");
+        assert_eq!(format_compact_number(12.25), "12.25");
+        assert_eq!(format_compact_number(100.0), "100");
+        assert_eq!(format_compact_number(1000.0), "1k");
+        assert_eq!(format_compact_number(1500.0), "1.5k");
+        assert_eq!(format_compact_number(-1500.0), "-1.5k");
+        assert_eq!(format_compact_number(125_000.0), "125k");
+        assert_eq!(format_compact_number(1_200_000.0), "1.2M");
+        assert_eq!(format_compact_number(2_500_000_000.0), "2.5B");
+    }
+}